@@ -71,6 +71,7 @@ impl MetaLearningEngine {
             enable_self_modification: true,
             max_modifications_per_cycle: 10,
             safety_check_enabled: true,
+            min_confidence_to_store: 0.0,
         };
 
         Self {
@@ -0,0 +1,132 @@
+//! # Edit-Distance
+//!
+//! Levenshtein distance and minimal edit scripts over any `PartialEq`
+//! slices, shared by `midstreamer-temporal-compare` and
+//! `midstreamer-text-transform`. Has no required dependencies, so crates
+//! that only need edit distance don't pull in the temporal comparator.
+//!
+//! ## Features
+//! - Levenshtein DP table with a caller-supplied equality
+//! - Distance and minimal edit scripts for slices
+//! - Serde support for `EditStep` (`serde` feature)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One step of an edit script produced by [`edit_script`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EditStep {
+    /// Keep the next source element
+    Keep,
+    /// Replace the next source element with the next target element
+    Substitute,
+    /// Drop the next source element
+    Delete,
+    /// Insert the next target element
+    Insert,
+}
+
+/// Levenshtein distance between `source` and `target`
+pub fn distance<T: PartialEq>(source: &[T], target: &[T]) -> usize {
+    let (n, m) = (source.len(), target.len());
+    levenshtein_table(n, m, |i, j| source[i] == target[j])[n][m]
+}
+
+/// Minimal (Levenshtein) edit script turning `source` into `target`
+///
+/// Steps consume `source` and `target` left to right; the number of
+/// non-`Keep` steps equals the edit distance. Where several scripts are
+/// minimal, matches are preferred over substitutions, then deletions.
+pub fn edit_script<T: PartialEq>(source: &[T], target: &[T]) -> Vec<EditStep> {
+    let (n, m) = (source.len(), target.len());
+    let dp = levenshtein_table(n, m, |i, j| source[i] == target[j]);
+
+    let mut steps = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && source[i - 1] == target[j - 1] && dp[i][j] == dp[i - 1][j - 1] {
+            steps.push(EditStep::Keep);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && dp[i][j] == dp[i - 1][j - 1] + 1 {
+            steps.push(EditStep::Substitute);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && dp[i][j] == dp[i - 1][j] + 1 {
+            steps.push(EditStep::Delete);
+            i -= 1;
+        } else {
+            steps.push(EditStep::Insert);
+            j -= 1;
+        }
+    }
+
+    steps.reverse();
+    steps
+}
+
+/// Levenshtein DP table for sequences of length `n` and `m`
+///
+/// `matches(i, j)` reports whether source element `i` equals target
+/// element `j`; cell `[i][j]` holds the distance between the prefixes.
+pub fn levenshtein_table(
+    n: usize,
+    m: usize,
+    matches: impl Fn(usize, usize) -> bool,
+) -> Vec<Vec<usize>> {
+    let mut dp = vec![vec![0; m + 1]; n + 1];
+
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = if matches(i - 1, j - 1) { 0 } else { 1 };
+
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+
+    dp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_and_script_agree() {
+        let cases = [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("same", "same"),
+        ];
+        for (a, b) in cases {
+            let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+            let edits = edit_script(&a, &b)
+                .into_iter()
+                .filter(|step| *step != EditStep::Keep)
+                .count();
+            assert_eq!(edits, distance(&a, &b));
+        }
+        assert_eq!(distance(&['k', 'i', 't'], &['s', 'i', 't', 's']), 2);
+    }
+
+    #[test]
+    fn test_edit_script_prefers_matches() {
+        assert_eq!(
+            edit_script(&[1, 2, 3], &[1, 3]),
+            vec![EditStep::Keep, EditStep::Delete, EditStep::Keep]
+        );
+        assert!(edit_script::<i32>(&[], &[]).is_empty());
+        assert_eq!(edit_script(&[], &[7, 8]), vec![EditStep::Insert; 2]);
+    }
+}
@@ -0,0 +1,83 @@
+//! Attractor-aware task priorities
+//!
+//! Maps the dynamical regime reported by `midstreamer-attractor` to a
+//! scheduling `Priority`, so monitoring work for a system that has turned
+//! chaotic is dispatched ahead of work for one that has settled.
+//!
+//! | Attractor type       | Priority |
+//! |----------------------|----------|
+//! | `StrangeAttractor`   | Critical |
+//! | `Unknown`            | High     |
+//! | `LimitCycle`         | Medium   |
+//! | `PointAttractor`     | Low      |
+//!
+//! `Unknown` ranks above the periodic and stable regimes because behavior
+//! that cannot be classified yet deserves a closer look.
+
+use crate::{Deadline, Priority, RealtimeScheduler, SchedulerError};
+use midstreamer_attractor::{AttractorInfo, AttractorType};
+
+/// Priority for tasks monitoring a system with the given attractor
+pub fn attractor_to_priority(info: &AttractorInfo) -> Priority {
+    match info.attractor_type {
+        AttractorType::StrangeAttractor => Priority::Critical,
+        AttractorType::Unknown => Priority::High,
+        AttractorType::LimitCycle => Priority::Medium,
+        AttractorType::PointAttractor => Priority::Low,
+    }
+}
+
+impl<T: Send + 'static> RealtimeScheduler<T> {
+    /// Schedule a task with the priority `attractor_to_priority` assigns
+    /// to `info`
+    pub fn schedule_for_attractor(
+        &self,
+        payload: T,
+        deadline: Deadline,
+        info: &AttractorInfo,
+    ) -> Result<u64, SchedulerError> {
+        self.schedule(payload, deadline, attractor_to_priority(info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(attractor_type: AttractorType) -> AttractorInfo {
+        AttractorInfo {
+            attractor_type,
+            dimension: 3,
+            lyapunov_exponents: vec![0.0; 3],
+            is_stable: attractor_type == AttractorType::PointAttractor,
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_attractor_to_priority() {
+        let chaotic = attractor_to_priority(&info(AttractorType::StrangeAttractor));
+        let cycle = attractor_to_priority(&info(AttractorType::LimitCycle));
+        let stable = attractor_to_priority(&info(AttractorType::PointAttractor));
+
+        assert!(chaotic > cycle);
+        assert!(cycle > stable);
+        assert_eq!(chaotic, Priority::Critical);
+    }
+
+    #[test]
+    fn test_schedule_for_attractor() {
+        let scheduler = RealtimeScheduler::default();
+        let deadline = Deadline::from_millis(100);
+
+        scheduler
+            .schedule_for_attractor("stable", deadline, &info(AttractorType::PointAttractor))
+            .unwrap();
+        scheduler
+            .schedule_for_attractor("chaotic", deadline, &info(AttractorType::StrangeAttractor))
+            .unwrap();
+
+        assert_eq!(scheduler.next_task().unwrap().payload, "chaotic");
+        assert_eq!(scheduler.next_task().unwrap().payload, "stable");
+    }
+}
@@ -9,10 +9,13 @@
 //! - Lock-free queues for performance
 //! - CPU affinity support
 
+#[cfg(feature = "attractor")]
+pub mod attractor;
+
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -34,7 +37,7 @@ pub enum SchedulerError {
 }
 
 /// Priority levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Priority {
     Critical = 100,
     High = 75,
@@ -133,12 +136,52 @@ impl<T> PartialOrd for ScheduledTask<T> {
 
 impl<T> Ord for ScheduledTask<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        // Higher priority first, earlier deadline first
-        other.priority.cmp(&self.priority).then_with(|| {
-            self.deadline
-                .absolute_time
-                .cmp(&other.deadline.absolute_time)
-        })
+        // BinaryHeap pops the greatest task: higher priority first, then
+        // earlier deadline, then lower id (FIFO among otherwise equal tasks)
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| {
+                other
+                    .deadline
+                    .absolute_time
+                    .cmp(&self.deadline.absolute_time)
+            })
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+/// Read-only view of a pending task, as returned by `queue_snapshot`
+#[derive(Debug, Clone, Copy)]
+pub struct TaskView {
+    pub id: u64,
+    pub priority: Priority,
+    pub deadline: Deadline,
+}
+
+impl<T> From<&ScheduledTask<T>> for TaskView {
+    fn from(task: &ScheduledTask<T>) -> Self {
+        Self {
+            id: task.id,
+            priority: task.priority,
+            deadline: task.deadline,
+        }
+    }
+}
+
+/// Pending tasks added and removed between two queue snapshots
+#[derive(Debug, Clone, Default)]
+pub struct QueueDiff {
+    /// Tasks pending now but not in the earlier snapshot, in priority order
+    pub added: Vec<TaskView>,
+    /// Tasks in the earlier snapshot that are no longer pending (dispatched,
+    /// cancelled or cleared), in snapshot order
+    pub removed: Vec<TaskView>,
+}
+
+impl QueueDiff {
+    /// True when the queue holds the same tasks as the earlier snapshot
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
     }
 }
 
@@ -147,12 +190,38 @@ impl<T> Ord for ScheduledTask<T> {
 pub struct SchedulerStats {
     pub total_tasks: u64,
     pub completed_tasks: u64,
+    /// Tasks whose deadline had already passed when execution started
     pub missed_deadlines: u64,
+    /// Tasks that started on time but passed their deadline while running
+    #[serde(default)]
+    pub overran_during_execution: u64,
     pub average_latency_ns: u64,
     pub max_latency_ns: u64,
     pub queue_size: usize,
 }
 
+/// Observed execution latency for one priority class
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+    pub average_ns: u64,
+}
+
+impl LatencyStats {
+    fn record(&mut self, latency_ns: u64) {
+        if self.count == 0 || latency_ns < self.min_ns {
+            self.min_ns = latency_ns;
+        }
+        self.max_ns = self.max_ns.max(latency_ns);
+
+        let total = self.average_ns as u128 * self.count as u128 + latency_ns as u128;
+        self.count += 1;
+        self.average_ns = (total / self.count as u128) as u64;
+    }
+}
+
 /// Configuration for the scheduler
 #[derive(Debug, Clone)]
 pub struct SchedulerConfig {
@@ -177,6 +246,7 @@ impl Default for SchedulerConfig {
 pub struct RealtimeScheduler<T> {
     task_queue: Arc<RwLock<BinaryHeap<ScheduledTask<T>>>>,
     stats: Arc<RwLock<SchedulerStats>>,
+    latency_by_priority: Arc<RwLock<HashMap<Priority, LatencyStats>>>,
     config: SchedulerConfig,
     next_task_id: Arc<RwLock<u64>>,
     running: Arc<RwLock<bool>>,
@@ -191,10 +261,12 @@ impl<T: Send + 'static> RealtimeScheduler<T> {
                 total_tasks: 0,
                 completed_tasks: 0,
                 missed_deadlines: 0,
+                overran_during_execution: 0,
                 average_latency_ns: 0,
                 max_latency_ns: 0,
                 queue_size: 0,
             })),
+            latency_by_priority: Arc::new(RwLock::new(HashMap::new())),
             config,
             next_task_id: Arc::new(RwLock::new(0)),
             running: Arc::new(RwLock::new(false)),
@@ -251,7 +323,8 @@ impl<T: Send + 'static> RealtimeScheduler<T> {
         let execution_start = Instant::now();
 
         // Check if deadline was missed
-        if task.deadline.is_passed() {
+        let started_late = task.deadline.is_passed();
+        if started_late {
             let mut stats = self.stats.write();
             stats.missed_deadlines += 1;
         }
@@ -259,10 +332,22 @@ impl<T: Send + 'static> RealtimeScheduler<T> {
         // Execute the task
         f(task.payload);
 
+        // Started on time but ran past the deadline
+        if !started_late && task.deadline.is_passed() {
+            let mut stats = self.stats.write();
+            stats.overran_during_execution += 1;
+        }
+
         // Update statistics
         let execution_time = execution_start.elapsed();
         let latency_ns = execution_time.as_nanos() as u64;
 
+        self.latency_by_priority
+            .write()
+            .entry(task.priority)
+            .or_default()
+            .record(latency_ns);
+
         let mut stats = self.stats.write();
         stats.completed_tasks += 1;
 
@@ -296,6 +381,13 @@ impl<T: Send + 'static> RealtimeScheduler<T> {
         self.stats.read().clone()
     }
 
+    /// Execution latency (min/max/average) per priority class
+    ///
+    /// Only priorities that have executed at least one task are present.
+    pub fn stats_by_priority(&self) -> HashMap<Priority, LatencyStats> {
+        self.latency_by_priority.read().clone()
+    }
+
     /// Clear all pending tasks
     pub fn clear(&self) {
         let mut queue = self.task_queue.write();
@@ -309,6 +401,50 @@ impl<T: Send + 'static> RealtimeScheduler<T> {
     pub fn queue_size(&self) -> usize {
         self.task_queue.read().len()
     }
+
+    /// Remove a pending task
+    ///
+    /// Returns false if no task with `task_id` is queued.
+    pub fn cancel(&self, task_id: u64) -> bool {
+        let mut queue = self.task_queue.write();
+        let before = queue.len();
+        queue.retain(|task| task.id != task_id);
+        if queue.len() == before {
+            return false;
+        }
+
+        let mut stats = self.stats.write();
+        stats.queue_size = queue.len();
+        true
+    }
+
+    /// All pending tasks in dispatch order, without removing them
+    pub fn queue_snapshot(&self) -> Vec<TaskView> {
+        let queue = self.task_queue.read();
+        let mut tasks: Vec<&ScheduledTask<T>> = queue.iter().collect();
+        tasks.sort_unstable_by(|a, b| b.cmp(a));
+        tasks.into_iter().map(TaskView::from).collect()
+    }
+
+    /// Tasks added to and removed from the queue since `prev` was taken
+    /// with `queue_snapshot`
+    pub fn queue_diff(&self, prev: &[TaskView]) -> QueueDiff {
+        let current = self.queue_snapshot();
+        let prev_ids: HashSet<u64> = prev.iter().map(|task| task.id).collect();
+        let current_ids: HashSet<u64> = current.iter().map(|task| task.id).collect();
+
+        QueueDiff {
+            added: current
+                .into_iter()
+                .filter(|task| !prev_ids.contains(&task.id))
+                .collect(),
+            removed: prev
+                .iter()
+                .filter(|task| !current_ids.contains(&task.id))
+                .copied()
+                .collect(),
+        }
+    }
 }
 
 impl<T: Send + 'static> Default for RealtimeScheduler<T> {
@@ -370,6 +506,21 @@ mod tests {
         assert_eq!(task3.payload, 1); // Low priority
     }
 
+    #[test]
+    fn test_fifo_among_equal_tasks() {
+        let scheduler = RealtimeScheduler::default();
+        let deadline = Deadline::from_millis(100);
+
+        for i in 0..8 {
+            scheduler.schedule(i, deadline, Priority::Medium).unwrap();
+        }
+
+        let order: Vec<i32> = std::iter::from_fn(|| scheduler.next_task())
+            .map(|task| task.payload)
+            .collect();
+        assert_eq!(order, (0..8).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_deadline_detection() {
         let scheduler = RealtimeScheduler::default();
@@ -402,6 +553,57 @@ mod tests {
         assert_eq!(stats.completed_tasks, 1);
     }
 
+    #[test]
+    fn test_overrun_during_execution() {
+        let scheduler = RealtimeScheduler::default();
+
+        scheduler
+            .schedule(1, Deadline::from_millis(50), Priority::High)
+            .unwrap();
+        let task = scheduler.next_task().unwrap();
+        scheduler.execute_task(task, |_| std::thread::sleep(Duration::from_millis(100)));
+
+        scheduler
+            .schedule(2, Deadline::from_millis(500), Priority::High)
+            .unwrap();
+        let task = scheduler.next_task().unwrap();
+        scheduler.execute_task(task, |_| {});
+
+        let stats = scheduler.stats();
+        assert_eq!(stats.completed_tasks, 2);
+        assert_eq!(stats.missed_deadlines, 0);
+        assert_eq!(stats.overran_during_execution, 1);
+    }
+
+    #[test]
+    fn test_stats_by_priority() {
+        let scheduler = RealtimeScheduler::default();
+
+        for (delay_ms, priority) in [(2, Priority::High), (6, Priority::High), (1, Priority::Low)] {
+            scheduler
+                .schedule(delay_ms, Deadline::from_millis(1000), priority)
+                .unwrap();
+        }
+        while let Some(task) = scheduler.next_task() {
+            scheduler.execute_task(task, |ms| std::thread::sleep(Duration::from_millis(ms)));
+        }
+
+        let by_priority = scheduler.stats_by_priority();
+        assert_eq!(by_priority.len(), 2);
+        assert!(!by_priority.contains_key(&Priority::Critical));
+
+        let high = &by_priority[&Priority::High];
+        assert_eq!(high.count, 2);
+        assert!(high.min_ns >= 2_000_000);
+        assert!(high.max_ns >= 6_000_000);
+        assert!(high.min_ns <= high.average_ns && high.average_ns <= high.max_ns);
+
+        let low = &by_priority[&Priority::Low];
+        assert_eq!(low.count, 1);
+        assert_eq!(low.min_ns, low.max_ns);
+        assert_eq!(low.average_ns, low.max_ns);
+    }
+
     #[test]
     fn test_stats() {
         let scheduler = RealtimeScheduler::default();
@@ -416,4 +618,38 @@ mod tests {
         assert_eq!(stats.total_tasks, 10);
         assert_eq!(stats.queue_size, 10);
     }
+
+    #[test]
+    fn test_queue_snapshot_and_diff() {
+        let scheduler = RealtimeScheduler::default();
+        let low = scheduler
+            .schedule(1, Deadline::from_millis(100), Priority::Low)
+            .unwrap();
+        let critical = scheduler
+            .schedule(2, Deadline::from_millis(100), Priority::Critical)
+            .unwrap();
+        let medium = scheduler
+            .schedule(3, Deadline::from_millis(100), Priority::Medium)
+            .unwrap();
+
+        let snapshot = scheduler.queue_snapshot();
+        let ids: Vec<u64> = snapshot.iter().map(|task| task.id).collect();
+        assert_eq!(ids, vec![critical, medium, low]);
+        assert_eq!(snapshot[0].priority, Priority::Critical);
+        assert_eq!(scheduler.queue_size(), 3);
+        assert!(scheduler.queue_diff(&snapshot).is_empty());
+
+        assert!(scheduler.cancel(medium));
+        assert!(!scheduler.cancel(medium));
+        let high = scheduler
+            .schedule(4, Deadline::from_millis(100), Priority::High)
+            .unwrap();
+
+        let diff = scheduler.queue_diff(&snapshot);
+        let added: Vec<u64> = diff.added.iter().map(|task| task.id).collect();
+        let removed: Vec<u64> = diff.removed.iter().map(|task| task.id).collect();
+        assert_eq!(added, vec![high]);
+        assert_eq!(removed, vec![medium]);
+        assert_eq!(scheduler.stats().queue_size, 3);
+    }
 }
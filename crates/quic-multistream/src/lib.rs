@@ -116,6 +116,19 @@ pub enum QuicError {
     IoError(String),
 }
 
+impl QuicError {
+    /// Whether the error may go away on its own, so the operation is worth
+    /// retrying (failed handshakes and timeouts)
+    pub fn is_transient(&self) -> bool {
+        match self {
+            QuicError::ConnectionFailed(_) | QuicError::Timeout(_) => true,
+            #[cfg(not(target_arch = "wasm32"))]
+            QuicError::QuinnError(quinn::ConnectionError::TimedOut) => true,
+            _ => false,
+        }
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 impl From<wasm_bindgen::JsValue> for QuicError {
     fn from(err: wasm_bindgen::JsValue) -> Self {
@@ -124,24 +137,19 @@ impl From<wasm_bindgen::JsValue> for QuicError {
 }
 
 /// Stream priority for quality-of-service control
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StreamPriority {
     /// Critical priority (highest)
     Critical = 0,
     /// High priority
     High = 1,
     /// Normal priority (default)
+    #[default]
     Normal = 2,
     /// Low priority
     Low = 3,
 }
 
-impl Default for StreamPriority {
-    fn default() -> Self {
-        StreamPriority::Normal
-    }
-}
-
 impl fmt::Display for StreamPriority {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,10 +1,15 @@
 //! Native QUIC implementation using quinn
 
 use crate::{ConnectionStats, QuicError, StreamPriority};
+use futures::Stream;
 use quinn::{ClientConfig, Endpoint, RecvStream, SendStream, VarInt};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
 use std::net::{SocketAddr, ToSocketAddrs};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 /// QUIC connection wrapper for native targets
 pub struct QuicConnection {
@@ -68,6 +73,39 @@ impl QuicConnection {
         })
     }
 
+    /// Connect, retrying transient failures with exponential backoff
+    ///
+    /// Attempt `n` (counting from 1) that fails with a transient error (see
+    /// `QuicError::is_transient`) is followed by a delay of
+    /// `base_delay * 2^(n-1)` plus a random extra of up to `jitter`. Other
+    /// errors are returned immediately; after `max_attempts` failed attempts
+    /// the last error is returned.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use midstreamer_quic::QuicConnection;
+    /// use std::time::Duration;
+    ///
+    /// let connection = QuicConnection::connect_with_retry(
+    ///     "localhost:4433",
+    ///     5,
+    ///     Duration::from_millis(100),
+    ///     Duration::from_millis(50),
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_retry(
+        addr: &str,
+        max_attempts: u32,
+        base_delay: Duration,
+        jitter: Duration,
+    ) -> Result<Self, QuicError> {
+        retry_transient(max_attempts, base_delay, jitter, || Self::connect(addr)).await
+    }
+
     /// Open a bidirectional stream
     pub async fn open_bi_stream(&self) -> Result<QuicStream, QuicError> {
         let (send, recv) = self.connection.open_bi().await?;
@@ -140,6 +178,47 @@ impl QuicConnection {
     }
 }
 
+/// Run `attempt` up to `max_attempts` times, sleeping between transient
+/// failures as described on `QuicConnection::connect_with_retry`
+async fn retry_transient<T, F, Fut>(
+    max_attempts: u32,
+    base_delay: Duration,
+    jitter: Duration,
+    mut attempt: F,
+) -> Result<T, QuicError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, QuicError>>,
+{
+    if max_attempts == 0 {
+        return Err(QuicError::InvalidConfig(
+            "max_attempts must be at least 1".to_string(),
+        ));
+    }
+
+    let mut tried = 0;
+    loop {
+        tried += 1;
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) if !e.is_transient() || tried >= max_attempts => return Err(e),
+            Err(_) => tokio::time::sleep(backoff_delay(tried, base_delay, jitter)).await,
+        }
+    }
+}
+
+/// Delay after failed attempt `tried`: exponential backoff plus random jitter
+fn backoff_delay(tried: u32, base_delay: Duration, jitter: Duration) -> Duration {
+    let backoff = base_delay.saturating_mul(1u32 << (tried - 1).min(16));
+
+    // Randomly seeded hasher as a dependency-free source of jitter
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(tried);
+    let fraction = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+
+    backoff.saturating_add(jitter.mul_f64(fraction))
+}
+
 /// Bidirectional QUIC stream
 pub struct QuicStream {
     send: SendStream,
@@ -180,6 +259,26 @@ impl QuicStream {
         Ok(n)
     }
 
+    /// Receive data as a stream of chunks, ending when the peer finishes
+    ///
+    /// Chunks are yielded in order as they arrive, so their boundaries need
+    /// not match the peer's writes. The stream ends after the first error.
+    pub fn recv_stream(&mut self) -> impl Stream<Item = Result<Vec<u8>, QuicError>> + '_ {
+        futures::stream::unfold(Some(self), |stream| async move {
+            let stream = stream?;
+            match stream.recv.read_chunk(usize::MAX, true).await {
+                Ok(Some(chunk)) => {
+                    stream
+                        .bytes_received
+                        .fetch_add(chunk.bytes.len() as u64, Ordering::Relaxed);
+                    Some((Ok(chunk.bytes.to_vec()), Some(stream)))
+                }
+                Ok(None) => None,
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        })
+    }
+
     /// Finish sending on this stream
     pub async fn finish(&mut self) -> Result<(), QuicError> {
         self.send
@@ -307,4 +406,148 @@ mod tests {
         assert_eq!(StreamPriority::default(), StreamPriority::Normal);
         assert!(StreamPriority::Critical < StreamPriority::High);
     }
+
+    #[test]
+    fn test_backoff_delay() {
+        let base = Duration::from_millis(10);
+        let jitter = Duration::from_millis(5);
+
+        for tried in 1..=4 {
+            let delay = backoff_delay(tried, base, jitter);
+            let backoff = base * (1 << (tried - 1));
+            assert!(delay >= backoff && delay <= backoff + jitter);
+        }
+        assert_eq!(backoff_delay(3, base, Duration::ZERO), base * 4);
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_gives_up() {
+        let mut attempts = 0;
+        let result: Result<(), QuicError> = retry_transient(
+            3,
+            Duration::from_millis(1),
+            Duration::from_millis(1),
+            || {
+                attempts += 1;
+                let attempt = attempts;
+                async move { Err(QuicError::ConnectionFailed(format!("attempt {}", attempt))) }
+            },
+        )
+        .await;
+
+        assert_eq!(attempts, 3);
+        match result {
+            Err(QuicError::ConnectionFailed(msg)) => assert_eq!(msg, "attempt 3"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_recovers_and_stops_on_permanent_errors() {
+        let mut attempts = 0;
+        let result = retry_transient(5, Duration::ZERO, Duration::ZERO, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt < 3 {
+                    Err(QuicError::Timeout("handshake".to_string()))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+
+        let mut attempts = 0;
+        let result: Result<(), QuicError> =
+            retry_transient(5, Duration::ZERO, Duration::ZERO, || {
+                attempts += 1;
+                async { Err(QuicError::TlsError("bad certificate".to_string())) }
+            })
+            .await;
+        assert_eq!(attempts, 1);
+        assert!(matches!(result, Err(QuicError::TlsError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_errors() {
+        // An unresolvable address is a configuration error, not retried
+        let result = QuicConnection::connect_with_retry(
+            "not a valid address",
+            3,
+            Duration::from_secs(10),
+            Duration::ZERO,
+        )
+        .await;
+        assert!(matches!(result, Err(QuicError::InvalidConfig(_))));
+
+        let result =
+            QuicConnection::connect_with_retry("127.0.0.1:4433", 0, Duration::ZERO, Duration::ZERO)
+                .await;
+        assert!(matches!(result, Err(QuicError::InvalidConfig(_))));
+    }
+
+    /// Local QUIC server that answers the first bidirectional stream with
+    /// `chunks`, pausing between writes, then finishes the stream
+    async fn chunk_server(chunks: &'static [&'static [u8]]) -> SocketAddr {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let mut server_crypto = quinn::rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![cert.serialize_der().unwrap().into()],
+                quinn::rustls::pki_types::PrivatePkcs8KeyDer::from(
+                    cert.serialize_private_key_der(),
+                )
+                .into(),
+            )
+            .unwrap();
+        server_crypto.alpn_protocols = vec![b"h3".to_vec()];
+
+        let server_config = quinn::ServerConfig::with_crypto(Arc::new(
+            quinn::crypto::rustls::QuicServerConfig::try_from(server_crypto).unwrap(),
+        ));
+        let endpoint = Endpoint::server(server_config, "127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = endpoint.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let connection = endpoint.accept().await.unwrap().await.unwrap();
+            let (mut send, _recv) = connection.accept_bi().await.unwrap();
+            for chunk in chunks {
+                send.write_all(chunk).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            send.finish().unwrap();
+            connection.closed().await;
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_recv_stream_yields_chunks_until_finished() {
+        use futures::StreamExt;
+
+        let chunks: &[&[u8]] = &[b"one", b"two", b"three"];
+        let addr = chunk_server(chunks).await;
+
+        let connection = QuicConnection::connect(&addr.to_string()).await.unwrap();
+        let mut stream = connection.open_bi_stream().await.unwrap();
+        // The server only sees the stream once data is sent on it
+        stream.send(b"start").await.unwrap();
+
+        // Chunk boundaries are not preserved, so only the bytes and the end
+        // of the stream are checked
+        let received: Vec<Vec<u8>> = tokio::time::timeout(
+            Duration::from_secs(10),
+            stream.recv_stream().map(|chunk| chunk.unwrap()).collect(),
+        )
+        .await
+        .expect("stream ends once the server finishes");
+
+        assert!(received.iter().all(|chunk| !chunk.is_empty()));
+        assert_eq!(received.concat(), b"onetwothree");
+        assert_eq!(connection.stats().bytes_received, 11);
+        connection.close(0, b"done");
+    }
 }
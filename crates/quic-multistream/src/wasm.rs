@@ -1,6 +1,7 @@
 //! WASM implementation using WebTransport
 
 use crate::{ConnectionStats, QuicError, StreamPriority};
+use futures::Stream;
 use js_sys::{Promise, Uint8Array};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -206,6 +207,24 @@ impl QuicStream {
         Ok(to_copy)
     }
 
+    /// Receive data as a stream of chunks, ending when the peer finishes
+    ///
+    /// The stream ends after the first error.
+    pub fn recv_stream(&mut self) -> impl Stream<Item = Result<Vec<u8>, QuicError>> + '_ {
+        futures::stream::unfold(Some(self), |stream| async move {
+            let stream = stream?;
+            let mut buf = vec![0u8; 65536];
+            match stream.recv(&mut buf).await {
+                Ok(0) => None,
+                Ok(n) => {
+                    buf.truncate(n);
+                    Some((Ok(buf), Some(stream)))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
     /// Finish sending on this stream
     pub async fn finish(&mut self) -> Result<(), QuicError> {
         let writable = self.bi_stream.writable();
@@ -36,6 +36,8 @@ fn recursive_optimization_benchmark(c: &mut Criterion) {
                 enable_self_modification: false,
                 max_modifications_per_cycle: 5,
                 safety_check_enabled: true,
+                min_confidence_to_store: 0.0,
+                attractor_cache_size: 0,
             };
 
             // Generate sample data
@@ -71,6 +73,8 @@ fn self_modification_overhead_benchmark(c: &mut Criterion) {
                     enable_self_modification: true,
                     max_modifications_per_cycle: 100,
                     safety_check_enabled: true,
+                    min_confidence_to_store: 0.0,
+                    attractor_cache_size: 0,
                 };
 
                 b.iter(|| {
@@ -110,6 +114,8 @@ fn meta_learning_convergence_benchmark(c: &mut Criterion) {
                     enable_self_modification: false,
                     max_modifications_per_cycle: 5,
                     safety_check_enabled: true,
+                    min_confidence_to_store: 0.0,
+                    attractor_cache_size: 0,
                 };
 
                 b.iter(|| {
@@ -149,6 +155,8 @@ fn memory_usage_recursion_benchmark(c: &mut Criterion) {
                     enable_self_modification: false,
                     max_modifications_per_cycle: 5,
                     safety_check_enabled: true,
+                    min_confidence_to_store: 0.0,
+                    attractor_cache_size: 0,
                 };
 
                 let data: Vec<String> = (0..*data_size)
@@ -186,6 +194,8 @@ fn strategy_adaptation_speed_benchmark(c: &mut Criterion) {
                     enable_self_modification: false,
                     max_modifications_per_cycle: 5,
                     safety_check_enabled: true,
+                    min_confidence_to_store: 0.0,
+                    attractor_cache_size: 0,
                 };
 
                 b.iter(|| {
@@ -220,11 +230,13 @@ fn safety_constraint_checking_benchmark(c: &mut Criterion) {
             BenchmarkId::from_parameter(num_constraints),
             num_constraints,
             |b, &num_constraints| {
-                let mut config = StrangeLoopConfig {
+                let config = StrangeLoopConfig {
                     max_meta_depth: 2,
                     enable_self_modification: true,
                     max_modifications_per_cycle: 100,
                     safety_check_enabled: true,
+                    min_confidence_to_store: 0.0,
+                    attractor_cache_size: 0,
                 };
 
                 b.iter(|| {
@@ -10,7 +10,7 @@
 //! - Meta-knowledge extraction
 
 use dashmap::DashMap;
-use midstreamer_attractor::{AttractorAnalyzer, PhasePoint};
+use midstreamer_attractor::{AnalysisCacheStats, AttractorAnalyzer, PhasePoint};
 use midstreamer_neural_solver::TemporalNeuralSolver;
 use midstreamer_temporal_compare::TemporalComparator;
 use serde::{Deserialize, Serialize};
@@ -144,6 +144,12 @@ pub struct StrangeLoopConfig {
     pub enable_self_modification: bool,
     pub max_modifications_per_cycle: usize,
     pub safety_check_enabled: bool,
+    /// Patterns with lower confidence are dropped instead of stored
+    /// (0.0 stores everything)
+    pub min_confidence_to_store: f64,
+    /// Number of `analyze_behavior` results cached by trajectory contents
+    /// (0 disables the cache)
+    pub attractor_cache_size: usize,
 }
 
 impl Default for StrangeLoopConfig {
@@ -153,6 +159,8 @@ impl Default for StrangeLoopConfig {
             enable_self_modification: false, // Disabled by default for safety
             max_modifications_per_cycle: 5,
             safety_check_enabled: true,
+            min_confidence_to_store: 0.0,
+            attractor_cache_size: 0,
         }
     }
 }
@@ -179,7 +187,6 @@ impl StrangeLoop {
     /// Create a new strange loop
     pub fn new(config: StrangeLoopConfig) -> Self {
         Self {
-            config,
             meta_knowledge: Arc::new(DashMap::new()),
             safety_constraints: vec![
                 SafetyConstraint::always_safe(),
@@ -190,12 +197,17 @@ impl StrangeLoop {
             modification_count: 0,
             safety_violations: 0,
             temporal_comparator: TemporalComparator::new(1000, 10000),
-            attractor_analyzer: AttractorAnalyzer::new(3, 10000),
+            attractor_analyzer: AttractorAnalyzer::new(3, 10000)
+                .with_cache(config.attractor_cache_size),
             temporal_solver: TemporalNeuralSolver::default(),
+            config,
         }
     }
 
     /// Learn at a specific meta-level
+    ///
+    /// Returns the patterns that were stored; those below
+    /// `min_confidence_to_store` are dropped.
     pub fn learn_at_level(
         &mut self,
         level: MetaLevel,
@@ -215,10 +227,7 @@ impl StrangeLoop {
         let patterns = self.extract_patterns(level, data)?;
 
         // Store meta-knowledge
-        self.meta_knowledge
-            .entry(level)
-            .or_insert_with(Vec::new)
-            .extend(patterns.clone());
+        let patterns = self.store_knowledge(level, patterns);
 
         // If not at max depth, meta-learn from this level
         if level.level() < self.config.max_meta_depth {
@@ -228,6 +237,22 @@ impl StrangeLoop {
         Ok(patterns)
     }
 
+    /// Store patterns meeting the confidence floor and return them
+    fn store_knowledge(
+        &self,
+        level: MetaLevel,
+        mut patterns: Vec<MetaKnowledge>,
+    ) -> Vec<MetaKnowledge> {
+        patterns.retain(|k| k.confidence >= self.config.min_confidence_to_store);
+
+        self.meta_knowledge
+            .entry(level)
+            .or_default()
+            .extend(patterns.clone());
+
+        patterns
+    }
+
     /// Meta-learn from a lower level
     fn meta_learn_from_level(&mut self, level: MetaLevel) -> Result<(), StrangeLoopError> {
         // Get knowledge from this level
@@ -361,6 +386,38 @@ impl StrangeLoop {
         }
     }
 
+    /// Overall learning quality: per-level average confidence, weighted per level
+    ///
+    /// `level_weights[i]` is the weight of `MetaLevel(i)`. Levels without
+    /// knowledge or without a weight are left out of the aggregate.
+    pub fn weighted_learning_score(&self, level_weights: &[f64]) -> f64 {
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+
+        for entry in self.meta_knowledge.iter() {
+            let knowledge = entry.value();
+            if knowledge.is_empty() {
+                continue;
+            }
+
+            let Some(&weight) = level_weights.get(entry.key().level()) else {
+                continue;
+            };
+
+            let average_confidence =
+                knowledge.iter().map(|k| k.confidence).sum::<f64>() / knowledge.len() as f64;
+
+            weighted_sum += weight * average_confidence;
+            total_weight += weight;
+        }
+
+        if total_weight > 0.0 {
+            weighted_sum / total_weight
+        } else {
+            0.0
+        }
+    }
+
     /// Reset the strange loop
     pub fn reset(&mut self) {
         self.meta_knowledge.clear();
@@ -389,6 +446,11 @@ impl StrangeLoop {
 
         Ok(format!("{:?}", analysis.attractor_type))
     }
+
+    /// Statistics of the `analyze_behavior` result cache
+    pub fn attractor_cache_stats(&self) -> AnalysisCacheStats {
+        self.attractor_analyzer.cache_stats()
+    }
 }
 
 impl Default for StrangeLoop {
@@ -442,6 +504,68 @@ mod tests {
         assert!(!knowledge.is_empty());
     }
 
+    #[test]
+    fn test_min_confidence_to_store() {
+        let config = StrangeLoopConfig {
+            min_confidence_to_store: 0.5,
+            ..StrangeLoopConfig::default()
+        };
+        let strange_loop = StrangeLoop::new(config);
+        let level = MetaLevel::base();
+
+        let stored = strange_loop.store_knowledge(
+            level,
+            vec![
+                MetaKnowledge::new(level, "noise".to_string(), 0.4),
+                MetaKnowledge::new(level, "signal".to_string(), 0.8),
+            ],
+        );
+        assert_eq!(stored.len(), 1);
+
+        let knowledge = strange_loop.get_knowledge_at_level(level);
+        assert_eq!(knowledge.len(), 1);
+        assert_eq!(knowledge[0].pattern, "signal");
+
+        // A floor above every extracted pattern keeps the store empty
+        let mut strict = StrangeLoop::new(StrangeLoopConfig {
+            min_confidence_to_store: 0.9,
+            ..StrangeLoopConfig::default()
+        });
+        let data = vec!["a".to_string(), "a".to_string()];
+        assert!(strict.learn_at_level(level, &data).unwrap().is_empty());
+        assert!(strict.get_knowledge_at_level(level).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_behavior_cache() {
+        let mut strange_loop = StrangeLoop::new(StrangeLoopConfig {
+            attractor_cache_size: 4,
+            ..StrangeLoopConfig::default()
+        });
+        let trajectory: Vec<Vec<f64>> = (0..120)
+            .map(|i| {
+                let t = i as f64 * 0.1;
+                vec![t.sin(), t.cos(), t]
+            })
+            .collect();
+
+        let first = strange_loop.analyze_behavior(trajectory).unwrap();
+        assert_eq!(strange_loop.attractor_cache_stats().misses, 1);
+
+        // No new points: the unchanged trajectory is served from the cache
+        let second = strange_loop.analyze_behavior(Vec::new()).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(strange_loop.attractor_cache_stats().hits, 1);
+
+        // Off by default
+        let mut uncached = StrangeLoop::default();
+        uncached.analyze_behavior(vec![vec![0.0; 3]; 120]).unwrap();
+        assert_eq!(
+            uncached.attractor_cache_stats(),
+            AnalysisCacheStats::default()
+        );
+    }
+
     #[test]
     fn test_max_depth_exceeded() {
         let mut strange_loop = StrangeLoop::default();
@@ -482,6 +606,33 @@ mod tests {
         assert_eq!(summary.safety_violations, 0);
     }
 
+    #[test]
+    fn test_weighted_learning_score() {
+        let strange_loop = StrangeLoop::default();
+
+        strange_loop.meta_knowledge.insert(
+            MetaLevel(0),
+            vec![
+                MetaKnowledge::new(MetaLevel(0), "a".to_string(), 0.6),
+                MetaKnowledge::new(MetaLevel(0), "b".to_string(), 0.8),
+            ],
+        );
+        strange_loop.meta_knowledge.insert(
+            MetaLevel(1),
+            vec![MetaKnowledge::new(MetaLevel(1), "c".to_string(), 0.9)],
+        );
+
+        // (0.7 * 1.0 + 0.9 * 3.0) / 4.0
+        let score = strange_loop.weighted_learning_score(&[1.0, 3.0]);
+        assert!((score - 0.85).abs() < 1e-9);
+
+        // Only level 0 has a weight
+        let score = strange_loop.weighted_learning_score(&[2.0]);
+        assert!((score - 0.7).abs() < 1e-9);
+
+        assert_eq!(strange_loop.weighted_learning_score(&[]), 0.0);
+    }
+
     #[test]
     fn test_reset() {
         let mut strange_loop = StrangeLoop::default();
@@ -8,9 +8,15 @@
 //! - Phase space analysis
 //! - Trajectory visualization data
 //! - Stability detection
+//! - Trajectory comparison (DTW over phase points)
 
+use midstreamer_temporal_compare::IncrementalDtw;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use thiserror::Error;
 
 /// Attractor analysis errors
@@ -22,6 +28,12 @@ pub enum AttractorError {
     #[error("Invalid dimension: {0}")]
     InvalidDimension(usize),
 
+    #[error("Invalid dimension: {1} (after adding {0} points)")]
+    BatchDimensionMismatch(usize, usize),
+
+    #[error("Invalid neighbor count: {0} (trajectory has {1} points)")]
+    InvalidNeighborCount(usize, usize),
+
     #[error("Computation error: {0}")]
     ComputationError(String),
 }
@@ -94,6 +106,9 @@ impl Trajectory {
     }
 }
 
+/// Maximum difference in largest Lyapunov exponent for `AttractorInfo::similar_to`
+pub const LYAPUNOV_SIMILARITY_TOLERANCE: f64 = 0.1;
+
 /// Information about a detected attractor
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttractorInfo {
@@ -109,14 +124,67 @@ impl AttractorInfo {
         matches!(self.attractor_type, AttractorType::StrangeAttractor)
     }
 
+    /// Largest finite Lyapunov exponent; `None` if there is none
     pub fn max_lyapunov_exponent(&self) -> Option<f64> {
-        self.lyapunov_exponents
-            .iter()
-            .copied()
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        max_exponent(&self.lyapunov_exponents)
+    }
+
+    /// Whether two analyses describe the same kind of attractor
+    ///
+    /// Requires the same attractor type and dimension, and largest Lyapunov
+    /// exponents within [`LYAPUNOV_SIMILARITY_TOLERANCE`].
+    pub fn similar_to(&self, other: &AttractorInfo) -> bool {
+        self.attractor_type == other.attractor_type
+            && self.dimension == other.dimension
+            && self
+                .lyapunov_difference(other)
+                .is_some_and(|diff| diff <= LYAPUNOV_SIMILARITY_TOLERANCE)
+    }
+
+    /// Similarity score in [0, 1] (1.0 = identical classification)
+    ///
+    /// Weighted blend of attractor type match (0.5), dimension ratio (0.2)
+    /// and closeness of the largest Lyapunov exponents (0.3).
+    pub fn similarity(&self, other: &AttractorInfo) -> f64 {
+        let type_score = if self.attractor_type == other.attractor_type {
+            1.0
+        } else {
+            0.0
+        };
+
+        let dimension_score = if self.dimension == other.dimension {
+            1.0
+        } else {
+            self.dimension.min(other.dimension) as f64 / self.dimension.max(other.dimension) as f64
+        };
+
+        let lyapunov_score = self
+            .lyapunov_difference(other)
+            .map_or(0.0, |diff| (-diff / LYAPUNOV_SIMILARITY_TOLERANCE).exp());
+
+        0.5 * type_score + 0.2 * dimension_score + 0.3 * lyapunov_score
+    }
+
+    /// Absolute difference of the largest Lyapunov exponents; `Some(0.0)` if
+    /// neither has a finite exponent, `None` if only one does
+    fn lyapunov_difference(&self, other: &AttractorInfo) -> Option<f64> {
+        match (self.max_lyapunov_exponent(), other.max_lyapunov_exponent()) {
+            (Some(a), Some(b)) => Some((a - b).abs()),
+            (None, None) => Some(0.0),
+            _ => None,
+        }
     }
 }
 
+/// Largest finite exponent, or `None` if every exponent is NaN or infinite
+fn max_exponent(exponents: &[f64]) -> Option<f64> {
+    exponents
+        .iter()
+        .copied()
+        .filter(|x| x.is_finite())
+        .max_by(f64::total_cmp)
+}
+
 /// Behavior summary statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BehaviorSummary {
@@ -127,11 +195,50 @@ pub struct BehaviorSummary {
     pub trajectory_length: f64,
 }
 
+/// Default number of nearest neighbors for neighbor-based estimators
+pub const DEFAULT_NEIGHBOR_COUNT: usize = 1;
+
+/// Neighbors closer than this many steps in time are ignored (Theiler window)
+const THEILER_WINDOW: usize = 10;
+
+/// Number of steps over which neighbor divergence is followed
+const DIVERGENCE_HORIZON: usize = 5;
+
+/// Hit/miss counts of the `AttractorAnalyzer` result cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+}
+
+impl AnalysisCacheStats {
+    /// Fraction of lookups served from the cache (0.0 if there were none)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Analysis results keyed by a hash of the trajectory coordinates
+struct AnalysisCache {
+    entries: Mutex<HashMap<u64, AttractorInfo>>,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
 /// Attractor analyzer
 pub struct AttractorAnalyzer {
     embedding_dimension: usize,
     min_points_for_analysis: usize,
+    neighbor_count: usize,
     trajectory: Trajectory,
+    cache: Option<AnalysisCache>,
 }
 
 impl AttractorAnalyzer {
@@ -140,10 +247,66 @@ impl AttractorAnalyzer {
         Self {
             embedding_dimension,
             min_points_for_analysis: 100,
+            neighbor_count: DEFAULT_NEIGHBOR_COUNT,
             trajectory: Trajectory::new(max_trajectory_length),
+            cache: None,
         }
     }
 
+    /// Builder: Cache up to `capacity` `analyze` results keyed by the
+    /// trajectory coordinates
+    ///
+    /// Analyzing a trajectory identical to a cached one returns the stored
+    /// result without recomputing it. When full, an arbitrary entry is
+    /// evicted. A capacity of 0 disables caching.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = (capacity > 0).then(|| AnalysisCache {
+            entries: Mutex::new(HashMap::with_capacity(capacity)),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        });
+        self
+    }
+
+    /// Cache statistics (all zero when caching is disabled)
+    pub fn cache_stats(&self) -> AnalysisCacheStats {
+        match &self.cache {
+            Some(cache) => AnalysisCacheStats {
+                hits: cache.hits.load(Ordering::Relaxed),
+                misses: cache.misses.load(Ordering::Relaxed),
+                size: cache.entries.lock().map_or(0, |entries| entries.len()),
+            },
+            None => AnalysisCacheStats::default(),
+        }
+    }
+
+    /// Drop all cached results and reset the statistics
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            if let Ok(mut entries) = cache.entries.lock() {
+                entries.clear();
+            }
+            cache.hits.store(0, Ordering::Relaxed);
+            cache.misses.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Builder: Set the number of nearest neighbors used by neighbor-based
+    /// estimators such as `largest_lyapunov_exponent`
+    ///
+    /// Must be non-zero and smaller than the trajectory length at estimation
+    /// time; otherwise the estimator returns `InvalidNeighborCount`.
+    pub fn with_neighbor_count(mut self, k: usize) -> Self {
+        self.neighbor_count = k;
+        self
+    }
+
+    /// Number of nearest neighbors used by neighbor-based estimators
+    pub fn neighbor_count(&self) -> usize {
+        self.neighbor_count
+    }
+
     /// Add a point to the trajectory
     pub fn add_point(&mut self, point: PhasePoint) -> Result<(), AttractorError> {
         if point.dimension() != self.embedding_dimension {
@@ -154,6 +317,40 @@ impl AttractorAnalyzer {
         Ok(())
     }
 
+    /// Add a batch of points to the trajectory
+    ///
+    /// Returns the number of points added. Stops at the first point with the
+    /// wrong dimension; points before it are kept and counted in the error.
+    pub fn add_points(
+        &mut self,
+        points: impl IntoIterator<Item = PhasePoint>,
+    ) -> Result<usize, AttractorError> {
+        let points = points.into_iter();
+
+        // Reserve up front instead of growing once per point
+        let room = self
+            .trajectory
+            .max_length
+            .saturating_sub(self.trajectory.len());
+        self.trajectory
+            .points
+            .reserve(points.size_hint().0.min(room));
+
+        let mut added = 0;
+        for point in points {
+            if point.dimension() != self.embedding_dimension {
+                return Err(AttractorError::BatchDimensionMismatch(
+                    added,
+                    point.dimension(),
+                ));
+            }
+            self.trajectory.push(point);
+            added += 1;
+        }
+
+        Ok(added)
+    }
+
     /// Analyze the current trajectory
     pub fn analyze(&self) -> Result<AttractorInfo, AttractorError> {
         if self.trajectory.len() < self.min_points_for_analysis {
@@ -162,6 +359,48 @@ impl AttractorAnalyzer {
             ));
         }
 
+        let Some(cache) = &self.cache else {
+            return self.analyze_uncached();
+        };
+
+        let key = self.trajectory_hash();
+        if let Some(info) = cache
+            .entries
+            .lock()
+            .ok()
+            .and_then(|entries| entries.get(&key).cloned())
+        {
+            cache.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(info);
+        }
+        cache.misses.fetch_add(1, Ordering::Relaxed);
+
+        let info = self.analyze_uncached()?;
+        if let Ok(mut entries) = cache.entries.lock() {
+            if entries.len() >= cache.capacity {
+                if let Some(evicted) = entries.keys().next().copied() {
+                    entries.remove(&evicted);
+                }
+            }
+            entries.insert(key, info.clone());
+        }
+        Ok(info)
+    }
+
+    /// Hash of the trajectory coordinates, the only input `analyze` reads
+    fn trajectory_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.trajectory.len().hash(&mut hasher);
+        for point in &self.trajectory.points {
+            for value in &point.coordinates {
+                value.to_bits().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Run the analysis on a trajectory already known to be long enough
+    fn analyze_uncached(&self) -> Result<AttractorInfo, AttractorError> {
         // Calculate Lyapunov exponents
         let lyapunov_exponents = self.calculate_lyapunov_exponents()?;
 
@@ -195,7 +434,7 @@ impl AttractorAnalyzer {
         // In production, this would use more sophisticated methods
         let points: Vec<&PhasePoint> = self.trajectory.points.iter().collect();
 
-        for dim in 0..self.embedding_dimension {
+        for (dim, exponent) in exponents.iter_mut().enumerate() {
             let mut sum_log_divergence = 0.0;
             let mut count = 0;
 
@@ -208,20 +447,127 @@ impl AttractorAnalyzer {
             }
 
             if count > 0 {
-                exponents[dim] = sum_log_divergence / count as f64;
+                *exponent = sum_log_divergence / count as f64;
             }
         }
 
         Ok(exponents)
     }
 
+    /// Estimate the largest Lyapunov exponent (per step) with Rosenstein's method
+    ///
+    /// Each point is paired with its `neighbor_count` nearest neighbors
+    /// (excluding temporally close points); the exponent is the slope of the
+    /// mean log divergence of those pairs over the next few steps.
+    pub fn largest_lyapunov_exponent(&self) -> Result<f64, AttractorError> {
+        self.largest_lyapunov_over_prefix(self.trajectory.len())
+    }
+
+    /// Running largest-Lyapunov estimate over increasing trajectory prefixes
+    ///
+    /// Returns `steps` estimates for prefix lengths spaced evenly from the
+    /// shortest usable prefix up to the full trajectory, so the last entry
+    /// equals `largest_lyapunov_exponent()`. A curve that has not leveled off
+    /// by the end suggests the trajectory is too short. Prefixes without an
+    /// estimate yield NaN; the result is empty if `steps` is zero or the
+    /// trajectory is too short for any estimate.
+    pub fn lyapunov_convergence(&self, steps: usize) -> Vec<f64> {
+        let n = self.trajectory.len();
+        let shortest = (THEILER_WINDOW + DIVERGENCE_HORIZON + 2).max(self.neighbor_count + 1);
+        if steps == 0 || n < shortest {
+            return Vec::new();
+        }
+
+        (1..=steps)
+            .map(|step| {
+                let len = shortest + (n - shortest) * step / steps;
+                self.largest_lyapunov_over_prefix(len).unwrap_or(f64::NAN)
+            })
+            .collect()
+    }
+
+    /// `largest_lyapunov_exponent` restricted to the first `n` points
+    fn largest_lyapunov_over_prefix(&self, n: usize) -> Result<f64, AttractorError> {
+        let k = self.neighbor_count;
+        if k == 0 || k >= n {
+            return Err(AttractorError::InvalidNeighborCount(k, n));
+        }
+
+        let min_points = THEILER_WINDOW + DIVERGENCE_HORIZON + 2;
+        if n < min_points {
+            return Err(AttractorError::InsufficientData(min_points));
+        }
+
+        let points: Vec<&[f64]> = self
+            .trajectory
+            .points
+            .iter()
+            .take(n)
+            .map(|p| p.coordinates.as_slice())
+            .collect();
+        let distance = |a: &[f64], b: &[f64]| -> f64 {
+            a.iter()
+                .zip(b)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        let references = n - DIVERGENCE_HORIZON;
+        let mut log_sum = [0.0; DIVERGENCE_HORIZON + 1];
+        let mut log_count = [0usize; DIVERGENCE_HORIZON + 1];
+        let mut candidates: Vec<(f64, usize)> = Vec::with_capacity(references);
+
+        for i in 0..references {
+            candidates.clear();
+            candidates.extend(
+                (0..references)
+                    .filter(|&j| i.abs_diff(j) > THEILER_WINDOW)
+                    .map(|j| (distance(points[i], points[j]), j))
+                    .filter(|(d, _)| *d > 0.0 && d.is_finite()),
+            );
+
+            let take = k.min(candidates.len());
+            if take == 0 {
+                continue;
+            }
+            candidates.select_nth_unstable_by(take - 1, |a, b| {
+                a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            for &(_, j) in &candidates[..take] {
+                for t in 0..=DIVERGENCE_HORIZON {
+                    let d = distance(points[i + t], points[j + t]);
+                    if d > 0.0 && d.is_finite() {
+                        log_sum[t] += d.ln();
+                        log_count[t] += 1;
+                    }
+                }
+            }
+        }
+
+        // Least-squares slope of mean log divergence against step
+        let curve: Vec<(f64, f64)> = (0..=DIVERGENCE_HORIZON)
+            .filter(|&t| log_count[t] > 0)
+            .map(|t| (t as f64, log_sum[t] / log_count[t] as f64))
+            .collect();
+        if curve.len() < 2 {
+            return Err(AttractorError::ComputationError(
+                "no diverging neighbor pairs".to_string(),
+            ));
+        }
+
+        let mean_t = curve.iter().map(|(t, _)| t).sum::<f64>() / curve.len() as f64;
+        let mean_y = curve.iter().map(|(_, y)| y).sum::<f64>() / curve.len() as f64;
+        let covariance: f64 = curve.iter().map(|(t, y)| (t - mean_t) * (y - mean_y)).sum();
+        let variance: f64 = curve.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+
+        Ok(covariance / variance)
+    }
+
     /// Classify attractor based on Lyapunov exponents
     fn classify_attractor(&self, lyapunov_exponents: &[f64]) -> AttractorType {
-        let max_exponent = lyapunov_exponents
-            .iter()
-            .copied()
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .unwrap_or(0.0);
+        let max_exponent = max_exponent(lyapunov_exponents).unwrap_or(0.0);
 
         if max_exponent > 0.1 {
             // Positive Lyapunov exponent indicates chaos
@@ -338,6 +684,191 @@ impl Default for AttractorAnalyzer {
     }
 }
 
+/// Number of histogram bins per axis for mutual information estimates
+const MI_BINS: usize = 16;
+
+/// Estimate the embedding delay of a scalar series as the first local
+/// minimum of the average mutual information (Fraser & Swinney, 1986)
+///
+/// Mutual information between `x[t]` and `x[t + delay]` is estimated from a
+/// `MI_BINS` × `MI_BINS` histogram for each delay from 1 to `max_delay`.
+/// Each value is split linearly between its two nearest bins, which keeps
+/// binning artifacts from creating spurious minima. Returns `max_delay` if
+/// the curve has no minimum in range, and 1 for constant or too-short
+/// series.
+pub fn estimate_time_delay(series: &[f64], max_delay: usize) -> usize {
+    let max_delay = max_delay.max(1);
+    let (min, max) = series
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+            (lo.min(x), hi.max(x))
+        });
+    if series.len() < 2 || (max - min).partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+        return 1;
+    }
+
+    // Lower bin and the weight of the upper one for every value
+    let bins: Vec<(usize, f64)> = series
+        .iter()
+        .map(|&x| {
+            let position = (x - min) / (max - min) * (MI_BINS - 1) as f64;
+            let lower = (position as usize).min(MI_BINS - 2);
+            (lower, position - lower as f64)
+        })
+        .collect();
+    let weights = |(lower, upper_weight): (usize, f64)| {
+        [(lower, 1.0 - upper_weight), (lower + 1, upper_weight)]
+    };
+
+    let mut previous = f64::INFINITY;
+    for delay in 1..=max_delay {
+        let pairs = bins.len().saturating_sub(delay);
+        if pairs < 2 {
+            return (delay - 1).max(1);
+        }
+
+        let mut joint = vec![0.0; MI_BINS * MI_BINS];
+        let mut first = [0.0; MI_BINS];
+        let mut second = [0.0; MI_BINS];
+        for (&a, &b) in bins.iter().zip(&bins[delay..]) {
+            for (bin_a, weight_a) in weights(a) {
+                first[bin_a] += weight_a;
+                for (bin_b, weight_b) in weights(b) {
+                    joint[bin_a * MI_BINS + bin_b] += weight_a * weight_b;
+                }
+            }
+            for (bin_b, weight_b) in weights(b) {
+                second[bin_b] += weight_b;
+            }
+        }
+
+        let n = pairs as f64;
+        let information: f64 = joint
+            .iter()
+            .enumerate()
+            .filter(|&(_, &mass)| mass > 0.0)
+            .map(|(cell, &mass)| {
+                let p = mass / n;
+                let p_first = first[cell / MI_BINS] / n;
+                let p_second = second[cell % MI_BINS] / n;
+                p * (p / (p_first * p_second)).ln()
+            })
+            .sum();
+
+        if information > previous {
+            return delay - 1;
+        }
+        previous = information;
+    }
+
+    max_delay
+}
+
+/// Distance growth ratio above which a neighbor counts as false
+const FNN_DISTANCE_RATIO: f64 = 15.0;
+
+/// Neighbor distance, relative to the series spread, above which a
+/// neighbor counts as false
+const FNN_SPREAD_RATIO: f64 = 2.0;
+
+/// False-neighbor fraction treated as "near zero"
+const FNN_FRACTION_THRESHOLD: f64 = 0.01;
+
+/// Estimate the embedding dimension of a scalar series with the
+/// false-nearest-neighbors method (Kennel et al., 1992)
+///
+/// For each dimension `d` from 1, the series is delay-embedded with the
+/// given `delay` and each point's nearest neighbor (outside the Theiler
+/// window) is checked in dimension `d + 1`: if the extra coordinate pulls
+/// them far apart, the neighbor was false. Returns the first dimension whose
+/// false-neighbor fraction is near zero, or `max_dim` if none is (at least 1).
+/// Stops early when the series is too short to embed the next dimension.
+pub fn estimate_embedding_dimension(series: &[f64], delay: usize, max_dim: usize) -> usize {
+    let delay = delay.max(1);
+    let max_dim = max_dim.max(1);
+
+    let mean = series.iter().sum::<f64>() / series.len().max(1) as f64;
+    let spread = (series.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+        / series.len().max(1) as f64)
+        .sqrt();
+
+    for dim in 1..=max_dim {
+        // Points need their (dim + 1)-th coordinate for the check
+        let count = series.len().saturating_sub(dim * delay);
+        if count <= THEILER_WINDOW + 1 {
+            return dim;
+        }
+
+        let mut false_neighbors = 0;
+        let mut checked = 0;
+        for i in 0..count {
+            let nearest = (0..count)
+                .filter(|&j| i.abs_diff(j) > THEILER_WINDOW)
+                .map(|j| {
+                    let distance_sq: f64 = (0..dim)
+                        .map(|k| (series[i + k * delay] - series[j + k * delay]).powi(2))
+                        .sum();
+                    (j, distance_sq)
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            let Some((j, distance_sq)) = nearest else {
+                continue;
+            };
+            if distance_sq <= 0.0 {
+                continue;
+            }
+
+            let extra = (series[i + dim * delay] - series[j + dim * delay]).abs();
+            let distance = distance_sq.sqrt();
+            let grown = (distance_sq + extra * extra).sqrt();
+            checked += 1;
+            if extra / distance > FNN_DISTANCE_RATIO || grown / spread > FNN_SPREAD_RATIO {
+                false_neighbors += 1;
+            }
+        }
+
+        if checked > 0 && false_neighbors as f64 / checked as f64 <= FNN_FRACTION_THRESHOLD {
+            return dim;
+        }
+    }
+
+    max_dim
+}
+
+/// DTW distance between two trajectories in phase space
+///
+/// The local cost of matching two points is the Euclidean distance between
+/// their coordinates plus `time_weight` times the gap between their
+/// timestamps, so a weight of 0 compares shape alone. Negative or NaN
+/// weights are treated as 0. Coordinates beyond the shorter point are
+/// ignored. As with `TemporalComparator`, the distance is `n + m` if either
+/// trajectory is empty.
+pub fn compare_trajectories(a: &Trajectory, b: &Trajectory, time_weight: f64) -> f64 {
+    let time_weight = time_weight.max(0.0);
+    let template: Vec<&PhasePoint> = b.points.iter().collect();
+    let mut dtw = IncrementalDtw::with_cost(template, |p: &&PhasePoint, q: &&PhasePoint| {
+        let spatial = p
+            .coordinates
+            .iter()
+            .zip(&q.coordinates)
+            .map(|(x, y)| (x - y).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let temporal = if time_weight > 0.0 {
+            time_weight * p.timestamp.abs_diff(q.timestamp) as f64
+        } else {
+            0.0
+        };
+        spatial + temporal
+    });
+
+    for point in &a.points {
+        dtw.push(point);
+    }
+    dtw.distance()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -385,6 +916,62 @@ mod tests {
         assert!(!info.lyapunov_exponents.is_empty());
     }
 
+    #[test]
+    fn test_analysis_cache() {
+        let mut analyzer = AttractorAnalyzer::new(2, 1000).with_cache(8);
+        let points = (0..150).map(|i| PhasePoint::new(vec![i as f64, (i * 2) as f64], i));
+        analyzer.add_points(points).unwrap();
+
+        let first = analyzer.analyze().unwrap();
+        let second = analyzer.analyze().unwrap();
+        assert_eq!(first.lyapunov_exponents, second.lyapunov_exponents);
+        assert_eq!(
+            analyzer.cache_stats(),
+            AnalysisCacheStats {
+                hits: 1,
+                misses: 1,
+                size: 1
+            }
+        );
+
+        // A changed trajectory is a miss
+        analyzer
+            .add_point(PhasePoint::new(vec![0.0, 0.0], 150))
+            .unwrap();
+        analyzer.analyze().unwrap();
+        assert_eq!(analyzer.cache_stats().misses, 2);
+        assert_eq!(analyzer.cache_stats().hit_rate(), 1.0 / 3.0);
+
+        analyzer.clear_cache();
+        assert_eq!(analyzer.cache_stats(), AnalysisCacheStats::default());
+
+        // Off by default
+        let uncached = AttractorAnalyzer::new(2, 1000);
+        assert_eq!(uncached.cache_stats(), AnalysisCacheStats::default());
+    }
+
+    #[test]
+    fn test_add_points_batch() {
+        let mut analyzer = AttractorAnalyzer::new(2, 10000);
+
+        let points = (0..1000).map(|i| PhasePoint::new(vec![i as f64, (i * 2) as f64], i));
+        assert_eq!(analyzer.add_points(points).unwrap(), 1000);
+        assert_eq!(analyzer.trajectory_length(), 1000);
+
+        let batch = vec![
+            PhasePoint::new(vec![1.0, 2.0], 1000),
+            PhasePoint::new(vec![3.0, 4.0], 1001),
+            PhasePoint::new(vec![5.0], 1002), // Wrong dimension
+            PhasePoint::new(vec![6.0, 7.0], 1003),
+        ];
+        let result = analyzer.add_points(batch);
+        assert!(matches!(
+            result,
+            Err(AttractorError::BatchDimensionMismatch(2, 1))
+        ));
+        assert_eq!(analyzer.trajectory_length(), 1002);
+    }
+
     #[test]
     fn test_invalid_dimension() {
         let mut analyzer = AttractorAnalyzer::new(3, 1000);
@@ -430,12 +1017,50 @@ mod tests {
             confidence: 0.95,
         };
 
-        // Should not panic, should handle NaN gracefully
-        let max_exp = info.max_lyapunov_exponent();
-        assert!(max_exp.is_some());
-        // With NaN handling, should return one of the valid values
-        let val = max_exp.unwrap();
-        assert!(val.is_finite(), "Should not return NaN");
+        // NaN is skipped wherever it appears
+        assert_eq!(info.max_lyapunov_exponent(), Some(1.0));
+        for exponents in [vec![f64::NAN, 1.0, -0.5], vec![1.0, -0.5, f64::NAN]] {
+            let info = AttractorInfo {
+                lyapunov_exponents: exponents,
+                ..info.clone()
+            };
+            assert_eq!(info.max_lyapunov_exponent(), Some(1.0));
+        }
+
+        // A NaN exponent does not hide a positive one from classification
+        let analyzer = AttractorAnalyzer::new(3, 100);
+        assert_eq!(
+            analyzer.classify_attractor(&[f64::NAN, 0.5]),
+            AttractorType::StrangeAttractor
+        );
+        assert_eq!(
+            analyzer.classify_attractor(&[-0.5, f64::NAN]),
+            AttractorType::PointAttractor
+        );
+    }
+
+    #[test]
+    fn test_infinite_lyapunov_exponents_skipped() {
+        let info = AttractorInfo {
+            attractor_type: AttractorType::Unknown,
+            dimension: 3,
+            lyapunov_exponents: vec![f64::INFINITY, -0.5, f64::NEG_INFINITY],
+            is_stable: false,
+            confidence: 0.5,
+        };
+        assert_eq!(info.max_lyapunov_exponent(), Some(-0.5));
+
+        let diverged = AttractorInfo {
+            lyapunov_exponents: vec![f64::INFINITY, f64::NAN],
+            ..info
+        };
+        assert_eq!(diverged.max_lyapunov_exponent(), None);
+
+        let analyzer = AttractorAnalyzer::new(3, 100);
+        assert_eq!(
+            analyzer.classify_attractor(&[f64::INFINITY, -0.5]),
+            AttractorType::PointAttractor
+        );
     }
 
     #[test]
@@ -477,8 +1102,181 @@ mod tests {
             confidence: 0.5,
         };
 
-        // Should not panic even with all NaN
-        let max_exp = info.max_lyapunov_exponent();
-        assert!(max_exp.is_some());
+        // Should not panic even with all NaN, and there is no maximum
+        assert_eq!(info.max_lyapunov_exponent(), None);
+    }
+
+    #[test]
+    fn test_attractor_similarity() {
+        let cycle = |lyapunov: f64| AttractorInfo {
+            attractor_type: AttractorType::LimitCycle,
+            dimension: 2,
+            lyapunov_exponents: vec![lyapunov, -0.4],
+            is_stable: true,
+            confidence: 0.9,
+        };
+        let chaotic = AttractorInfo {
+            attractor_type: AttractorType::StrangeAttractor,
+            dimension: 2,
+            lyapunov_exponents: vec![0.9, -1.2],
+            is_stable: false,
+            confidence: 0.9,
+        };
+
+        let a = cycle(0.0);
+        let b = cycle(0.05);
+        assert!(a.similar_to(&b));
+        assert!(a.similarity(&b) > 0.8);
+        assert_eq!(a.similarity(&a), 1.0);
+
+        assert!(!a.similar_to(&chaotic));
+        assert!(a.similarity(&chaotic) < 0.5);
+        assert!(a.similarity(&chaotic) < a.similarity(&b));
+
+        // Same type but Lyapunov exponents too far apart
+        assert!(!a.similar_to(&cycle(0.5)));
+    }
+
+    /// Logistic map at r = 4 embedded as (x[n], x[n+1]); largest exponent ln 2
+    fn logistic_analyzer(points: usize) -> AttractorAnalyzer {
+        let mut analyzer = AttractorAnalyzer::new(2, 10_000);
+        let mut x: f64 = 0.3;
+        for i in 0..points {
+            let next = 4.0 * x * (1.0 - x);
+            analyzer
+                .add_point(PhasePoint::new(vec![x, next], i as u64))
+                .unwrap();
+            x = next;
+        }
+        analyzer
+    }
+
+    #[test]
+    fn test_neighbor_count() {
+        let estimates: Vec<f64> = [1, 3, 5]
+            .iter()
+            .map(|&k| {
+                logistic_analyzer(800)
+                    .with_neighbor_count(k)
+                    .largest_lyapunov_exponent()
+                    .unwrap()
+            })
+            .collect();
+
+        for estimate in &estimates {
+            assert!(
+                (estimate - std::f64::consts::LN_2).abs() < 0.1,
+                "estimate {} far from ln 2",
+                estimate
+            );
+        }
+        assert_ne!(estimates[0], estimates[1]);
+        assert_ne!(estimates[1], estimates[2]);
+
+        let analyzer = logistic_analyzer(50).with_neighbor_count(50);
+        assert!(matches!(
+            analyzer.largest_lyapunov_exponent(),
+            Err(AttractorError::InvalidNeighborCount(50, 50))
+        ));
+        assert!(logistic_analyzer(50)
+            .with_neighbor_count(0)
+            .largest_lyapunov_exponent()
+            .is_err());
+    }
+
+    #[test]
+    fn test_estimate_embedding_dimension() {
+        // x-component of the Lorenz system (sigma 10, rho 28, beta 8/3)
+        let (dt, sigma, rho, beta) = (0.01, 10.0, 28.0, 8.0 / 3.0);
+        let (mut x, mut y, mut z): (f64, f64, f64) = (1.0, 1.0, 1.0);
+        let mut series = Vec::new();
+        for step in 0..5000 {
+            let (dx, dy, dz) = (sigma * (y - x), x * (rho - z) - y, x * y - beta * z);
+            x += dx * dt;
+            y += dy * dt;
+            z += dz * dt;
+            // Skip the transient and sample every other step
+            if step >= 1000 && step % 2 == 0 {
+                series.push(x);
+            }
+        }
+
+        let dim = estimate_embedding_dimension(&series, 5, 8);
+        assert!((3..=4).contains(&dim), "dimension {} for Lorenz", dim);
+
+        // A sine wave unfolds in two dimensions
+        let sine: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.05).sin()).collect();
+        assert_eq!(estimate_embedding_dimension(&sine, 30, 8), 2);
+
+        assert_eq!(estimate_embedding_dimension(&[], 1, 5), 1);
+    }
+
+    #[test]
+    fn test_estimate_time_delay() {
+        // Quarter period of a sine with period 100 is 25 samples
+        let sine: Vec<f64> = (0..2000)
+            .map(|i| (i as f64 * 2.0 * std::f64::consts::PI / 100.0).sin())
+            .collect();
+        let delay = estimate_time_delay(&sine, 60);
+        assert!((23..=27).contains(&delay), "delay {} for period 100", delay);
+
+        assert_eq!(estimate_time_delay(&[1.0; 50], 10), 1);
+        assert_eq!(estimate_time_delay(&[], 10), 1);
+    }
+
+    #[test]
+    fn test_compare_trajectories() {
+        let circle = |phase: f64| {
+            let mut traj = Trajectory::new(100);
+            for i in 0..50 {
+                let angle = i as f64 * 2.0 * std::f64::consts::PI / 25.0 + phase;
+                traj.push(PhasePoint::new(vec![angle.cos(), angle.sin()], i));
+            }
+            traj
+        };
+        let original = circle(0.0);
+        let shifted = circle(std::f64::consts::FRAC_PI_2);
+
+        assert!(compare_trajectories(&original, &original, 0.0) < 1e-12);
+        assert!(compare_trajectories(&original, &original, 1.0) < 1e-12);
+
+        // Warping absorbs most of the phase shift, but not the unmatched ends
+        let distance = compare_trajectories(&original, &shifted, 0.0);
+        assert!(distance > 0.1, "distance {} for shifted copy", distance);
+
+        // Warping across time costs extra once timestamps are weighted
+        assert!(compare_trajectories(&original, &shifted, 1.0) > distance);
+
+        let empty = Trajectory::new(10);
+        assert_eq!(compare_trajectories(&original, &empty, 0.0), 50.0);
+    }
+
+    #[test]
+    fn test_lyapunov_convergence() {
+        let analyzer = logistic_analyzer(1000);
+        let curve = analyzer.lyapunov_convergence(10);
+
+        assert_eq!(curve.len(), 10);
+        assert_eq!(
+            *curve.last().unwrap(),
+            analyzer.largest_lyapunov_exponent().unwrap()
+        );
+
+        // Later estimates settle near ln 2 and agree with each other
+        let tail = &curve[5..];
+        for estimate in tail {
+            assert!(*estimate > 0.0);
+            assert!(
+                (estimate - std::f64::consts::LN_2).abs() < 0.15,
+                "estimate {} far from ln 2",
+                estimate
+            );
+        }
+        let spread = tail.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+            - tail.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert!(spread < 0.1, "tail spread {}", spread);
+
+        assert!(analyzer.lyapunov_convergence(0).is_empty());
+        assert!(logistic_analyzer(10).lyapunov_convergence(5).is_empty());
     }
 }
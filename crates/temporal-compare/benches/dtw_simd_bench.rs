@@ -0,0 +1,27 @@
+//! Scalar vs SIMD DTW on f64 sequences
+//!
+//! Run with: cargo bench -p midstreamer-temporal-compare --features simd
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use midstreamer_temporal_compare::numeric::{dtw_distance, dtw_distance_simd};
+
+fn generate_sequence(len: usize, phase: f64) -> Vec<f64> {
+    (0..len).map(|i| (i as f64 * 0.05 + phase).sin()).collect()
+}
+
+fn bench_dtw_f64(c: &mut Criterion) {
+    let a = generate_sequence(512, 0.0);
+    let b = generate_sequence(512, 0.7);
+
+    let mut group = c.benchmark_group("dtw_f64_n512");
+    group.bench_function("scalar", |bench| {
+        bench.iter(|| dtw_distance(black_box(&a), black_box(&b)))
+    });
+    group.bench_function("simd", |bench| {
+        bench.iter(|| dtw_distance_simd(black_box(&a), black_box(&b)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_dtw_f64);
+criterion_main!(benches);
@@ -0,0 +1,180 @@
+//! DTW against a fixed template, updated one element at a time
+//!
+//! Streams are the rows of the DTW matrix and the template its columns.
+//! `IncrementalDtw` keeps only the last row, so each `push` costs O(m) time
+//! and the whole stream O(m) memory, where m is the template length. After
+//! n pushes the distance equals the batch DTW of the n elements against the
+//! template.
+
+/// Local cost used by [`IncrementalDtw::new`]: 0/1 value mismatch, as in
+/// `TemporalComparator::compare`
+pub type MatchCost<T> = fn(&T, &T) -> f64;
+
+/// Incremental DTW distance from a growing sequence to a fixed template
+///
+/// ```
+/// use midstreamer_temporal_compare::{numeric, IncrementalDtw};
+///
+/// let template = vec![1.0, 2.0, 3.0];
+/// let mut dtw = IncrementalDtw::with_cost(template.clone(), |a: &f64, b: &f64| (a - b).abs());
+/// for x in [1.0, 2.5, 3.0] {
+///     dtw.push(x);
+/// }
+/// assert_eq!(dtw.distance(), numeric::dtw_distance(&[1.0, 2.5, 3.0], &template));
+/// ```
+pub struct IncrementalDtw<T, F = MatchCost<T>> {
+    template: Vec<T>,
+    cost: F,
+    /// Last DTW row: `row[j]` is the distance to the first `j` template
+    /// elements
+    row: Vec<f64>,
+    /// Scratch buffer for the next row
+    next: Vec<f64>,
+    len: usize,
+}
+
+impl<T: PartialEq> IncrementalDtw<T> {
+    /// Create an incremental DTW with the 0/1 mismatch cost
+    pub fn new(template: Vec<T>) -> Self {
+        Self::with_cost(template, |a, b| if a == b { 0.0 } else { 1.0 })
+    }
+}
+
+impl<T, F> IncrementalDtw<T, F>
+where
+    F: Fn(&T, &T) -> f64,
+{
+    /// Create an incremental DTW with a caller-supplied local cost
+    pub fn with_cost(template: Vec<T>, cost: F) -> Self {
+        let m = template.len();
+        let mut row = vec![f64::INFINITY; m + 1];
+        row[0] = 0.0;
+
+        Self {
+            template,
+            cost,
+            row,
+            next: vec![f64::INFINITY; m + 1],
+            len: 0,
+        }
+    }
+
+    /// Append one element and return the updated distance to the template
+    pub fn push(&mut self, element: T) -> f64 {
+        self.next[0] = f64::INFINITY;
+        for j in 1..=self.template.len() {
+            let cost = (self.cost)(&element, &self.template[j - 1]);
+            let best = self.row[j - 1].min(self.row[j]).min(self.next[j - 1]);
+            self.next[j] = cost + best;
+        }
+        std::mem::swap(&mut self.row, &mut self.next);
+        self.len += 1;
+
+        self.distance()
+    }
+
+    /// DTW distance from the elements pushed so far to the template
+    ///
+    /// Like the batch DTW, this is `n + m` if either side is empty.
+    pub fn distance(&self) -> f64 {
+        if self.len == 0 || self.template.is_empty() {
+            return (self.len + self.template.len()) as f64;
+        }
+        self.row[self.template.len()]
+    }
+
+    /// Lower bound on the distance after any further pushes
+    ///
+    /// The smallest entry of the current DTW row. With non-negative costs
+    /// every later row, and so the final distance, is at least this, which
+    /// lets a caller abandon a match that can no longer beat a bound.
+    pub fn lower_bound(&self) -> f64 {
+        if self.template.is_empty() {
+            return self.distance();
+        }
+        self.row.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    /// Number of elements pushed so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if no elements have been pushed
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The template being matched against
+    pub fn template(&self) -> &[T] {
+        &self.template
+    }
+
+    /// Forget all pushed elements, keeping the template
+    pub fn reset(&mut self) {
+        self.row.fill(f64::INFINITY);
+        self.row[0] = 0.0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::dtw_distance;
+    use crate::{ComparisonAlgorithm, Sequence, TemporalComparator};
+
+    #[test]
+    fn test_incremental_matches_batch_dtw() {
+        let template = vec![1, 2, 3, 2, 1];
+        let stream = [1, 1, 2, 4, 3, 2, 2, 1, 5];
+        let comparator = TemporalComparator::new(10, 100);
+
+        let mut template_seq = Sequence::new();
+        for (i, &v) in template.iter().enumerate() {
+            template_seq.push(v, i as u64);
+        }
+
+        let mut dtw = IncrementalDtw::new(template.clone());
+        assert_eq!(dtw.distance(), template.len() as f64);
+
+        let mut prefix = Sequence::new();
+        let mut lower_bound = dtw.lower_bound();
+        for (i, &v) in stream.iter().enumerate() {
+            prefix.push(v, i as u64);
+            let batch = comparator
+                .compare(&prefix, &template_seq, ComparisonAlgorithm::DTW)
+                .unwrap();
+            assert_eq!(dtw.push(v), batch.distance, "after {} pushes", i + 1);
+
+            // The bound never decreases and never exceeds the distance
+            assert!(dtw.lower_bound() >= lower_bound);
+            assert!(dtw.lower_bound() <= dtw.distance());
+            lower_bound = dtw.lower_bound();
+        }
+        assert_eq!(dtw.len(), stream.len());
+
+        dtw.reset();
+        assert!(dtw.is_empty());
+        assert_eq!(dtw.push(1), 3.0);
+    }
+
+    #[test]
+    fn test_incremental_with_numeric_cost() {
+        let template = [0.5, 1.5, 3.0, 1.0];
+        let stream = [0.0, 1.0, 2.0, 3.5, 2.5, 0.5];
+
+        let mut dtw =
+            IncrementalDtw::with_cost(template.to_vec(), |a: &f64, b: &f64| (a - b).abs());
+        for n in 1..=stream.len() {
+            assert_eq!(
+                dtw.push(stream[n - 1]),
+                dtw_distance(&stream[..n], &template)
+            );
+        }
+
+        let mut empty = IncrementalDtw::new(Vec::<i32>::new());
+        assert_eq!(empty.push(7), 1.0);
+        assert_eq!(empty.push(7), 2.0);
+    }
+}
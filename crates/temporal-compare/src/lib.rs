@@ -8,17 +8,30 @@
 //! - Edit Distance (Levenshtein)
 //! - Pattern matching and detection
 //! - Efficient caching
+//! - Compact bincode serialization (`bincode` feature)
+//! - Numeric f64 DTW with an optional SIMD path (`simd` feature)
+//! - Incremental DTW against a fixed template for streaming input
 
 use dashmap::DashMap;
 use lru::LruCache;
+use midstreamer_edit_distance::levenshtein_table;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::hash_map::{DefaultHasher, Entry};
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
+pub mod incremental;
+pub mod numeric;
+
+pub use incremental::IncrementalDtw;
+pub use midstreamer_edit_distance::{edit_script, EditStep};
+
 /// Errors that can occur during temporal comparison
 #[derive(Debug, Error)]
 pub enum TemporalError {
@@ -36,6 +49,15 @@ pub enum TemporalError {
 
     #[error("Pattern not found")]
     PatternNotFound,
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Invalid band ratio: {0} (must be in (0, 1])")]
+    InvalidBandRatio(f64),
 }
 
 /// A temporal sequence element
@@ -69,6 +91,13 @@ impl<T> Sequence<T> {
     pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
     }
+
+    /// Whether timestamps never decrease (repeated timestamps are allowed)
+    pub fn is_monotonic(&self) -> bool {
+        self.elements
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp)
+    }
 }
 
 impl<T> Default for Sequence<T> {
@@ -77,17 +106,68 @@ impl<T> Default for Sequence<T> {
     }
 }
 
+#[cfg(feature = "bincode")]
+impl<T> Sequence<T>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    /// Serialize to compact bincode bytes (for on-disk caching)
+    pub fn to_bincode(&self) -> Result<Vec<u8>, TemporalError> {
+        bincode::serialize(self).map_err(|e| TemporalError::SerializationError(e.to_string()))
+    }
+
+    /// Deserialize from bytes produced by `to_bincode`
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, TemporalError> {
+        bincode::deserialize(bytes).map_err(|e| TemporalError::SerializationError(e.to_string()))
+    }
+}
+
 /// Comparison algorithm types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ComparisonAlgorithm {
     /// Dynamic Time Warping
     DTW,
+    /// Dynamic Time Warping restricted to a Sakoe-Chiba band of the given
+    /// half-width around the diagonal
+    DTWBanded(usize),
     /// Longest Common Subsequence
     LCS,
     /// Edit Distance (Levenshtein)
     EditDistance,
     /// Euclidean distance
     Euclidean,
+    /// Pearson correlation distance `1 - |r|` (numeric values only; see
+    /// `TemporalComparator::compare_numeric`)
+    Correlation,
+}
+
+/// Local cost between two numeric values for `compare_with_metric`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DistanceMetric {
+    /// 0.0 for equal values, 1.0 otherwise (the cost `compare` uses)
+    #[default]
+    Hamming,
+    /// `|a - b|`
+    AbsoluteDiff,
+    /// `(a - b)²`
+    Squared,
+}
+
+impl DistanceMetric {
+    /// Cost of aligning `a` with `b`
+    pub fn cost(&self, a: f64, b: f64) -> f64 {
+        match self {
+            DistanceMetric::Hamming => {
+                if a == b {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            DistanceMetric::AbsoluteDiff => (a - b).abs(),
+            DistanceMetric::Squared => (a - b) * (a - b),
+        }
+    }
 }
 
 /// Result of a temporal comparison
@@ -98,6 +178,18 @@ pub struct ComparisonResult {
     pub alignment: Option<Vec<(usize, usize)>>,
 }
 
+impl ComparisonResult {
+    /// Average cost per aligned step (distance / alignment path length)
+    ///
+    /// Returns `None` when the result carries no (or an empty) alignment.
+    pub fn mean_step_cost(&self) -> Option<f64> {
+        match self.alignment.as_ref() {
+            Some(path) if !path.is_empty() => Some(self.distance / path.len() as f64),
+            _ => None,
+        }
+    }
+}
+
 /// Statistics about cache performance
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheStats {
@@ -149,6 +241,22 @@ impl<T> Pattern<T> {
     }
 }
 
+#[cfg(feature = "bincode")]
+impl<T> Pattern<T>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    /// Serialize to compact bincode bytes (for on-disk caching)
+    pub fn to_bincode(&self) -> Result<Vec<u8>, TemporalError> {
+        bincode::serialize(self).map_err(|e| TemporalError::SerializationError(e.to_string()))
+    }
+
+    /// Deserialize from bytes produced by `to_bincode`
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, TemporalError> {
+        bincode::deserialize(bytes).map_err(|e| TemporalError::SerializationError(e.to_string()))
+    }
+}
+
 /// Match result for similarity search
 #[derive(Debug, Clone, PartialEq)]
 pub struct SimilarityMatch {
@@ -161,25 +269,111 @@ pub struct SimilarityMatch {
 }
 
 impl SimilarityMatch {
+    /// Create a match scored with the default normalization
     pub fn new(start_index: usize, distance: f64) -> Self {
-        // Convert distance to similarity score (inverse exponential decay)
-        let similarity = (-distance / 10.0).exp();
+        Self::with_normalization(start_index, distance, SimilarityNormalization::default())
+    }
+
+    /// Create a match whose similarity is `normalization` applied to `distance`
+    pub fn with_normalization(
+        start_index: usize,
+        distance: f64,
+        normalization: SimilarityNormalization,
+    ) -> Self {
         Self {
             start_index,
-            similarity,
+            similarity: normalization.similarity(distance),
             distance,
         }
     }
 }
 
+/// Default decay constant of `SimilarityNormalization::Exponential`
+pub const DEFAULT_SIMILARITY_DECAY: f64 = 10.0;
+
+/// Mapping from a distance (lower is better) to a similarity in `[0, 1]`
+///
+/// Pick the scale parameter to match the typical distances of the data: a
+/// scale suited to unit-step integer sequences makes every match of widely
+/// spread floats look dissimilar. A zero or negative `decay` or
+/// `max_distance` gives 1.0 for an exact match and 0.0 otherwise.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SimilarityNormalization {
+    /// `exp(-distance / decay)`
+    Exponential { decay: f64 },
+    /// `1 - distance / max_distance`, clamped to `[0, 1]`
+    Linear { max_distance: f64 },
+    /// `1 / (1 + exp(steepness * (distance - midpoint)))`, 0.5 at `midpoint`
+    Sigmoid { midpoint: f64, steepness: f64 },
+}
+
+impl Default for SimilarityNormalization {
+    fn default() -> Self {
+        SimilarityNormalization::Exponential {
+            decay: DEFAULT_SIMILARITY_DECAY,
+        }
+    }
+}
+
+impl SimilarityNormalization {
+    /// Similarity score for `distance`
+    pub fn similarity(&self, distance: f64) -> f64 {
+        match *self {
+            SimilarityNormalization::Exponential { decay } => {
+                if decay <= 0.0 {
+                    return exact_match_similarity(distance);
+                }
+                (-distance / decay).exp()
+            }
+            SimilarityNormalization::Linear { max_distance } => {
+                if max_distance <= 0.0 {
+                    return exact_match_similarity(distance);
+                }
+                (1.0 - distance / max_distance).clamp(0.0, 1.0)
+            }
+            SimilarityNormalization::Sigmoid {
+                midpoint,
+                steepness,
+            } => 1.0 / (1.0 + (steepness * (distance - midpoint)).exp()),
+        }
+    }
+}
+
+/// Similarity when the scale is zero or negative: 1.0 for an exact match,
+/// 0.0 otherwise
+fn exact_match_similarity(distance: f64) -> f64 {
+    if distance <= 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Direction of a monotonic run in a numeric sequence
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TrendDirection {
+    /// Strictly increasing values
+    Increasing,
+    /// Strictly decreasing values
+    Decreasing,
+    /// No strictly monotonic step (fewer than two values, or all equal)
+    Flat,
+}
+
 /// Temporal comparator with caching
 pub struct TemporalComparator<T> {
     cache: Arc<Mutex<LruCache<String, ComparisonResult>>>,
     pattern_cache: Arc<Mutex<LruCache<String, Vec<Pattern<T>>>>>,
     similarity_cache: Arc<Mutex<LruCache<String, Vec<SimilarityMatch>>>>,
+    pinned: Arc<DashMap<String, ComparisonResult>>,
     cache_hits: Arc<DashMap<String, u64>>,
     cache_misses: Arc<DashMap<String, u64>>,
     max_sequence_length: usize,
+    normalization: SimilarityNormalization,
+    /// Sequences stored with `add_sequence`, in insertion order
+    corpus: Vec<(String, Sequence<T>)>,
+    /// Position of each id in `corpus`
+    corpus_index: HashMap<String, usize>,
 }
 
 impl<T> TemporalComparator<T>
@@ -198,12 +392,22 @@ where
             similarity_cache: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(cache_size).unwrap(),
             ))),
+            pinned: Arc::new(DashMap::new()),
             cache_hits: Arc::new(DashMap::new()),
             cache_misses: Arc::new(DashMap::new()),
             max_sequence_length,
+            normalization: SimilarityNormalization::default(),
+            corpus: Vec::new(),
+            corpus_index: HashMap::new(),
         }
     }
 
+    /// Builder: Set how similarity search turns distances into similarities
+    pub fn with_normalization(mut self, normalization: SimilarityNormalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
     /// Compare two sequences using the specified algorithm
     pub fn compare(
         &self,
@@ -219,7 +423,12 @@ where
         // Generate cache key
         let cache_key = self.cache_key(seq1, seq2, algorithm);
 
-        // Check cache
+        // Check pinned entries, then the LRU cache
+        if let Some(result) = self.pinned.get(&cache_key) {
+            self.record_cache_hit(&cache_key);
+            return Ok(result.clone());
+        }
+
         if let Ok(mut cache) = self.cache.lock() {
             if let Some(result) = cache.get(&cache_key) {
                 self.record_cache_hit(&cache_key);
@@ -230,12 +439,7 @@ where
         self.record_cache_miss(&cache_key);
 
         // Compute comparison
-        let result = match algorithm {
-            ComparisonAlgorithm::DTW => self.dtw(seq1, seq2),
-            ComparisonAlgorithm::LCS => self.lcs(seq1, seq2),
-            ComparisonAlgorithm::EditDistance => self.edit_distance(seq1, seq2),
-            ComparisonAlgorithm::Euclidean => self.euclidean(seq1, seq2),
-        }?;
+        let result = self.compare_uncached(seq1, seq2, algorithm, |a, b| a == b)?;
 
         // Store in cache
         if let Ok(mut cache) = self.cache.lock() {
@@ -245,14 +449,342 @@ where
         Ok(result)
     }
 
+    /// Store `seq` under `id` for `knn` queries
+    ///
+    /// A sequence already stored under `id` is replaced and keeps its
+    /// original insertion position.
+    pub fn add_sequence(&mut self, id: String, seq: Sequence<T>) {
+        match self.corpus_index.entry(id) {
+            Entry::Occupied(entry) => self.corpus[*entry.get()].1 = seq,
+            Entry::Vacant(entry) => {
+                self.corpus.push((entry.key().clone(), seq));
+                entry.insert(self.corpus.len() - 1);
+            }
+        }
+    }
+
+    /// Number of sequences stored with `add_sequence`
+    pub fn corpus_len(&self) -> usize {
+        self.corpus.len()
+    }
+
+    /// The `k` stored sequences closest to `query`, nearest first
+    ///
+    /// Returns `(id, distance)` pairs; ties keep insertion order. A query
+    /// longer than the maximum sequence length is an error, as in `compare`;
+    /// stored sequences that cannot be compared are skipped. Results are not
+    /// cached.
+    pub fn knn(
+        &self,
+        query: &Sequence<T>,
+        k: usize,
+        algorithm: ComparisonAlgorithm,
+    ) -> Result<Vec<(String, f64)>, TemporalError> {
+        if query.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(query.len()));
+        }
+
+        let mut neighbors: Vec<(String, f64)> = self
+            .corpus
+            .iter()
+            .filter(|(_, seq)| seq.len() <= self.max_sequence_length)
+            .filter_map(|(id, seq)| {
+                self.compare_uncached(query, seq, algorithm, |a, b| a == b)
+                    .ok()
+                    .map(|result| (id.clone(), result.distance))
+            })
+            .collect();
+
+        neighbors.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        neighbors.truncate(k);
+        Ok(neighbors)
+    }
+
+    /// Compare where wildcard elements match any value
+    ///
+    /// Elements for which `is_wildcard` returns true (in either sequence)
+    /// count as equal to whatever they are aligned or substituted with, so
+    /// they add no cost. All algorithms are supported. Results are not
+    /// cached.
+    pub fn compare_with_wildcards(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+        is_wildcard: impl Fn(&T) -> bool,
+    ) -> Result<ComparisonResult, TemporalError> {
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
+        }
+
+        self.compare_uncached(seq1, seq2, algorithm, |a, b| {
+            a == b || is_wildcard(a) || is_wildcard(b)
+        })
+    }
+
+    /// Compare with DTW restricted to a band proportional to sequence length
+    ///
+    /// The band half-width is `(ratio * max(n, m)).ceil()` cells around the
+    /// diagonal, widened to `|n - m|` if needed so the end cell stays
+    /// reachable. `ratio` must be in `(0, 1]`; 1.0 is unconstrained DTW.
+    /// Only `ComparisonAlgorithm::DTW` supports a band. Results are not cached.
+    pub fn compare_with_band_ratio(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+        ratio: f64,
+    ) -> Result<ComparisonResult, TemporalError> {
+        if !(ratio > 0.0 && ratio <= 1.0) {
+            return Err(TemporalError::InvalidBandRatio(ratio));
+        }
+        if algorithm != ComparisonAlgorithm::DTW {
+            return Err(TemporalError::InvalidAlgorithm(format!(
+                "{:?} does not support a warping band",
+                algorithm
+            )));
+        }
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
+        }
+
+        let band = (ratio * seq1.len().max(seq2.len()) as f64).ceil() as usize;
+        self.dtw_with_band(seq1, seq2, band)
+    }
+
+    /// Compare with DTW that also penalizes aligning points far apart in time
+    ///
+    /// Each aligned pair costs its usual 0/1 value mismatch plus
+    /// `time_weight * |Δtimestamp|`. A `time_weight` of 0.0 is plain DTW;
+    /// negative or NaN weights are treated as 0.0. Only
+    /// `ComparisonAlgorithm::DTW` is time-aware. Results are not cached.
+    pub fn compare_time_aware(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+        time_weight: f64,
+    ) -> Result<ComparisonResult, TemporalError> {
+        if algorithm != ComparisonAlgorithm::DTW {
+            return Err(TemporalError::InvalidAlgorithm(format!(
+                "{:?} does not support timestamp penalties",
+                algorithm
+            )));
+        }
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
+        }
+
+        let time_weight = time_weight.max(0.0);
+        let band = seq1.len().max(seq2.len());
+        self.dtw_with_cost(seq1, seq2, band, |a, b| {
+            let mismatch = if a.value == b.value { 0.0 } else { 1.0 };
+            mismatch + time_weight * a.timestamp.abs_diff(b.timestamp) as f64
+        })
+    }
+
+    /// Compare numeric sequences with DTW using `metric` as the local cost
+    ///
+    /// `DistanceMetric::Hamming` gives the same distance as `compare`; the
+    /// other metrics keep magnitude, so 3.0 vs 3.1 costs far less than 3.0 vs
+    /// 99.0. See `compare_with_cost` for the supported algorithms.
+    pub fn compare_with_metric(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+        metric: DistanceMetric,
+    ) -> Result<ComparisonResult, TemporalError>
+    where
+        T: Copy + Into<f64>,
+    {
+        self.compare_with_cost(seq1, seq2, algorithm, |a, b| {
+            metric.cost((*a).into(), (*b).into())
+        })
+    }
+
+    /// Compare numeric sequences, including by `ComparisonAlgorithm::Correlation`
+    ///
+    /// Correlation pairs elements by position over the shorter length and
+    /// returns `1 - |r|` for the Pearson coefficient `r`, so perfectly
+    /// correlated or anti-correlated sequences are at distance 0.0. With
+    /// fewer than two pairs or a constant sequence `r` is undefined and the
+    /// distance is 1.0. Correlation results are not cached; other
+    /// algorithms go through `compare`.
+    pub fn compare_numeric(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+    ) -> Result<ComparisonResult, TemporalError>
+    where
+        T: Copy + Into<f64>,
+    {
+        if algorithm != ComparisonAlgorithm::Correlation {
+            return self.compare(seq1, seq2, algorithm);
+        }
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
+        }
+
+        let values = |seq: &Sequence<T>| -> Vec<f64> {
+            seq.elements.iter().map(|e| e.value.into()).collect()
+        };
+        let distance = pearson(&values(seq1), &values(seq2)).map_or(1.0, |r| 1.0 - r.abs());
+
+        Ok(ComparisonResult {
+            distance,
+            algorithm,
+            alignment: None,
+        })
+    }
+
+    /// Compare with DTW using a caller-supplied local cost between values
+    ///
+    /// Only `ComparisonAlgorithm::DTW` and `ComparisonAlgorithm::DTWBanded`
+    /// accumulate a real-valued cost; other algorithms are rejected. Results
+    /// are not cached.
+    pub fn compare_with_cost(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+        cost: impl Fn(&T, &T) -> f64,
+    ) -> Result<ComparisonResult, TemporalError> {
+        let band = match algorithm {
+            ComparisonAlgorithm::DTW => seq1.len().max(seq2.len()),
+            ComparisonAlgorithm::DTWBanded(band_width) => band_width,
+            _ => {
+                return Err(TemporalError::InvalidAlgorithm(format!(
+                    "{:?} does not support a custom cost",
+                    algorithm
+                )))
+            }
+        };
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
+        }
+
+        self.dtw_with_cost(seq1, seq2, band, |a, b| cost(&a.value, &b.value))
+            .map(|result| ComparisonResult {
+                algorithm,
+                ..result
+            })
+    }
+
+    /// Run `algorithm` with `matches` deciding which values are equal
+    fn compare_uncached<F>(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+        matches: F,
+    ) -> Result<ComparisonResult, TemporalError>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mismatch = |a: &TemporalElement<T>, b: &TemporalElement<T>| {
+            if matches(&a.value, &b.value) {
+                0.0
+            } else {
+                1.0
+            }
+        };
+
+        match algorithm {
+            ComparisonAlgorithm::DTW => {
+                let band = seq1.len().max(seq2.len());
+                self.dtw_with_cost(seq1, seq2, band, mismatch)
+            }
+            ComparisonAlgorithm::DTWBanded(band_width) => self
+                .dtw_with_cost(seq1, seq2, band_width, mismatch)
+                .map(|result| ComparisonResult {
+                    algorithm,
+                    ..result
+                }),
+            ComparisonAlgorithm::LCS => self.lcs(seq1, seq2, matches),
+            ComparisonAlgorithm::EditDistance => self.edit_distance(seq1, seq2, matches),
+            ComparisonAlgorithm::Euclidean => self.euclidean(seq1, seq2, matches),
+            ComparisonAlgorithm::Correlation => Err(TemporalError::InvalidAlgorithm(
+                "Correlation needs numeric values; use compare_numeric".to_string(),
+            )),
+        }
+    }
+
+    /// DTW restricted to a Sakoe-Chiba band of `band_width` cells around
+    /// the diagonal
+    ///
+    /// Only cells within the band are stored and filled, so time and memory
+    /// are O(n · band_width) instead of O(n · m). Cells outside the band
+    /// count as unreachable and the alignment never leaves it; the band is
+    /// widened to `|n - m|` so the end cell stays reachable. The result
+    /// reports `ComparisonAlgorithm::DTWBanded(band_width)`. Results are not
+    /// cached.
+    pub fn dtw_banded(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        band_width: usize,
+    ) -> Result<ComparisonResult, TemporalError> {
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
+        }
+
+        self.compare_uncached(
+            seq1,
+            seq2,
+            ComparisonAlgorithm::DTWBanded(band_width),
+            |a, b| a == b,
+        )
+    }
+
     /// Dynamic Time Warping implementation
     fn dtw(
         &self,
         seq1: &Sequence<T>,
         seq2: &Sequence<T>,
     ) -> Result<ComparisonResult, TemporalError> {
+        self.dtw_with_band(seq1, seq2, seq1.len().max(seq2.len()))
+    }
+
+    /// DTW restricted to cells within `band` of the diagonal
+    ///
+    /// Cells outside the band stay at infinity, so backtracking never leaves
+    /// it. The band is widened to `|n - m|` so the end cell is reachable.
+    fn dtw_with_band(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        band: usize,
+    ) -> Result<ComparisonResult, TemporalError> {
+        self.dtw_with_cost(
+            seq1,
+            seq2,
+            band,
+            |a, b| {
+                if a.value == b.value {
+                    0.0
+                } else {
+                    1.0
+                }
+            },
+        )
+    }
+
+    /// Banded DTW with a caller-supplied local cost between elements
+    fn dtw_with_cost<F>(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        band: usize,
+        cost: F,
+    ) -> Result<ComparisonResult, TemporalError>
+    where
+        F: Fn(&TemporalElement<T>, &TemporalElement<T>) -> f64,
+    {
         let n = seq1.len();
         let m = seq2.len();
+        let band = band.max(n.abs_diff(m));
 
         if n == 0 || m == 0 {
             return Ok(ComparisonResult {
@@ -262,20 +794,21 @@ where
             });
         }
 
-        // Initialize DTW matrix
-        let mut dtw = vec![vec![f64::INFINITY; m + 1]; n + 1];
-        dtw[0][0] = 0.0;
+        // Only cells within the band are stored: row i holds columns
+        // i - band ..= i + band (clamped to 0..=m)
+        let mut dtw = BandedMatrix::new(n, m, band);
+        dtw.set(0, 0, 0.0);
 
-        // Fill DTW matrix
+        // Fill DTW matrix within the band
         for i in 1..=n {
-            for j in 1..=m {
-                let cost = if seq1.elements[i - 1].value == seq2.elements[j - 1].value {
-                    0.0
-                } else {
-                    1.0
-                };
-
-                dtw[i][j] = cost + dtw[i - 1][j - 1].min(dtw[i - 1][j]).min(dtw[i][j - 1]);
+            for j in i.saturating_sub(band).max(1)..=(i + band).min(m) {
+                let cost = cost(&seq1.elements[i - 1], &seq2.elements[j - 1]);
+
+                let best = dtw
+                    .get(i - 1, j - 1)
+                    .min(dtw.get(i - 1, j))
+                    .min(dtw.get(i, j - 1));
+                dtw.set(i, j, cost + best);
             }
         }
 
@@ -286,12 +819,14 @@ where
         while i > 0 && j > 0 {
             alignment.push((i - 1, j - 1));
 
-            let min_val = dtw[i - 1][j - 1].min(dtw[i - 1][j]).min(dtw[i][j - 1]);
+            let diagonal = dtw.get(i - 1, j - 1);
+            let up = dtw.get(i - 1, j);
+            let min_val = diagonal.min(up).min(dtw.get(i, j - 1));
 
-            if dtw[i - 1][j - 1] == min_val {
+            if diagonal == min_val {
                 i -= 1;
                 j -= 1;
-            } else if dtw[i - 1][j] == min_val {
+            } else if up == min_val {
                 i -= 1;
             } else {
                 j -= 1;
@@ -301,7 +836,7 @@ where
         alignment.reverse();
 
         Ok(ComparisonResult {
-            distance: dtw[n][m],
+            distance: dtw.get(n, m),
             algorithm: ComparisonAlgorithm::DTW,
             alignment: Some(alignment),
         })
@@ -312,21 +847,14 @@ where
         &self,
         seq1: &Sequence<T>,
         seq2: &Sequence<T>,
+        matches: impl Fn(&T, &T) -> bool,
     ) -> Result<ComparisonResult, TemporalError> {
         let n = seq1.len();
         let m = seq2.len();
 
-        let mut dp = vec![vec![0; m + 1]; n + 1];
-
-        for i in 1..=n {
-            for j in 1..=m {
-                if seq1.elements[i - 1].value == seq2.elements[j - 1].value {
-                    dp[i][j] = dp[i - 1][j - 1] + 1;
-                } else {
-                    dp[i][j] = dp[i - 1][j].max(dp[i][j - 1]);
-                }
-            }
-        }
+        let dp = lcs_table(n, m, |i, j| {
+            matches(&seq1.elements[i].value, &seq2.elements[j].value)
+        });
 
         let lcs_length = dp[n][m];
         let distance = (n + m - 2 * lcs_length) as f64;
@@ -338,38 +866,57 @@ where
         })
     }
 
-    /// Edit Distance (Levenshtein) implementation
-    fn edit_distance(
+    /// The longest common subsequence of the values of `seq1` and `seq2`
+    ///
+    /// Backtracks the same table `ComparisonAlgorithm::LCS` builds. Among
+    /// equally long subsequences the choice is deterministic: when skipping
+    /// an element of either sequence keeps the same length, the element of
+    /// `seq1` is skipped (the upper cell is preferred). Empty input gives an
+    /// empty result; either sequence exceeding the maximum sequence length
+    /// gives `SequenceTooLong`, as in `compare`.
+    pub fn lcs_sequence(
         &self,
         seq1: &Sequence<T>,
         seq2: &Sequence<T>,
-    ) -> Result<ComparisonResult, TemporalError> {
-        let n = seq1.len();
-        let m = seq2.len();
-
-        let mut dp = vec![vec![0; m + 1]; n + 1];
-
-        for i in 0..=n {
-            dp[i][0] = i;
-        }
-        for j in 0..=m {
-            dp[0][j] = j;
+    ) -> Result<Vec<T>, TemporalError> {
+        if seq1.len() > self.max_sequence_length || seq2.len() > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(seq1.len().max(seq2.len())));
         }
 
-        for i in 1..=n {
-            for j in 1..=m {
-                let cost = if seq1.elements[i - 1].value == seq2.elements[j - 1].value {
-                    0
-                } else {
-                    1
-                };
+        let (n, m) = (seq1.len(), seq2.len());
+        let values_match = |i: usize, j: usize| seq1.elements[i].value == seq2.elements[j].value;
+        let dp = lcs_table(n, m, values_match);
 
-                dp[i][j] = (dp[i - 1][j] + 1)
-                    .min(dp[i][j - 1] + 1)
-                    .min(dp[i - 1][j - 1] + cost);
+        let mut subsequence = Vec::with_capacity(dp[n][m]);
+        let (mut i, mut j) = (n, m);
+        while i > 0 && j > 0 {
+            if values_match(i - 1, j - 1) {
+                subsequence.push(seq1.elements[i - 1].value.clone());
+                i -= 1;
+                j -= 1;
+            } else if dp[i - 1][j] >= dp[i][j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
             }
         }
 
+        subsequence.reverse();
+        Ok(subsequence)
+    }
+
+    /// Edit Distance (Levenshtein) implementation
+    fn edit_distance(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        matches: impl Fn(&T, &T) -> bool,
+    ) -> Result<ComparisonResult, TemporalError> {
+        let (n, m) = (seq1.len(), seq2.len());
+        let dp = levenshtein_table(n, m, |i, j| {
+            matches(&seq1.elements[i].value, &seq2.elements[j].value)
+        });
+
         Ok(ComparisonResult {
             distance: dp[n][m] as f64,
             algorithm: ComparisonAlgorithm::EditDistance,
@@ -382,13 +929,14 @@ where
         &self,
         seq1: &Sequence<T>,
         seq2: &Sequence<T>,
+        matches: impl Fn(&T, &T) -> bool,
     ) -> Result<ComparisonResult, TemporalError> {
         let n = seq1.len().min(seq2.len());
         let mut sum: f64 = 0.0;
 
         for i in 0..n {
             // Simplified: just count mismatches
-            if seq1.elements[i].value != seq2.elements[i].value {
+            if !matches(&seq1.elements[i].value, &seq2.elements[i].value) {
                 sum += 1.0;
             }
         }
@@ -401,16 +949,24 @@ where
     }
 
     /// Generate cache key for a comparison
+    ///
+    /// Keyed on the element values, not just the lengths, so different
+    /// sequences of equal length never share an entry. Timestamps are left
+    /// out since no cached algorithm depends on them.
     fn cache_key(
         &self,
         seq1: &Sequence<T>,
         seq2: &Sequence<T>,
         algorithm: ComparisonAlgorithm,
     ) -> String {
+        let values1: Vec<&T> = seq1.elements.iter().map(|e| &e.value).collect();
+        let values2: Vec<&T> = seq2.elements.iter().map(|e| &e.value).collect();
+
         format!(
-            "{:?}:{:?}:{:?}",
+            "{:?}:{:?}:{:016x}:{:?}",
             seq1.elements.len(),
             seq2.elements.len(),
+            content_hash(&[&values1[..], &values2[..]]),
             algorithm
         )
     }
@@ -448,6 +1004,39 @@ where
         }
     }
 
+    /// Pin a comparison result so LRU churn never evicts it
+    ///
+    /// Computes the result if it is not cached yet. Pinned entries survive
+    /// `clear_cache()`; use `unpin()` to release them.
+    pub fn pin(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+    ) -> Result<ComparisonResult, TemporalError> {
+        let result = self.compare(seq1, seq2, algorithm)?;
+        self.pinned
+            .insert(self.cache_key(seq1, seq2, algorithm), result.clone());
+        Ok(result)
+    }
+
+    /// Release a pinned comparison result. Returns `true` if it was pinned.
+    pub fn unpin(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        algorithm: ComparisonAlgorithm,
+    ) -> bool {
+        self.pinned
+            .remove(&self.cache_key(seq1, seq2, algorithm))
+            .is_some()
+    }
+
+    /// Number of pinned comparison results
+    pub fn pinned_count(&self) -> usize {
+        self.pinned.len()
+    }
+
     /// Clear the cache
     pub fn clear_cache(&self) {
         if let Ok(mut cache) = self.cache.lock() {
@@ -469,6 +1058,31 @@ where
         haystack: &[T],
         needle: &[T],
         threshold: f64,
+    ) -> Result<Vec<SimilarityMatch>, TemporalError> {
+        self.find_similar_impl(haystack, needle, threshold, None)
+    }
+
+    /// Cancellable variant of `find_similar_generic`
+    ///
+    /// `cancel` is checked before every window; once it is set the scan stops
+    /// and `TemporalError::Cancelled` is returned. Nothing is cached for a
+    /// cancelled scan.
+    pub fn find_similar_generic_cancellable(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        threshold: f64,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<SimilarityMatch>, TemporalError> {
+        self.find_similar_impl(haystack, needle, threshold, Some(cancel))
+    }
+
+    fn find_similar_impl(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        threshold: f64,
+        cancel: Option<&AtomicBool>,
     ) -> Result<Vec<SimilarityMatch>, TemporalError> {
         if needle.is_empty() || haystack.len() < needle.len() {
             return Ok(Vec::new());
@@ -476,9 +1090,10 @@ where
 
         // Generate cache key
         let cache_key = format!(
-            "similar:{:?}:{:?}:{}",
+            "similar:{:?}:{:?}:{:016x}:{}",
             haystack.len(),
             needle.len(),
+            content_hash(&[haystack, needle]),
             threshold
         );
 
@@ -493,10 +1108,224 @@ where
         self.record_cache_miss(&cache_key);
 
         let needle_len = needle.len();
-        let mut matches = Vec::new();
+        let mut matches: Vec<SimilarityMatch> = self
+            .window_distances(haystack, needle, cancel)?
+            .into_iter()
+            .filter(|&(_, distance)| distance / needle_len as f64 <= threshold)
+            .map(|(start_idx, distance)| {
+                SimilarityMatch::with_normalization(start_idx, distance, self.normalization)
+            })
+            .collect();
+
+        // Sort by distance (best matches first)
+        matches.sort_by(|a, b| {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // Store in cache
+        if let Ok(mut cache) = self.similarity_cache.lock() {
+            cache.put(cache_key, matches.clone());
+        }
+
+        Ok(matches)
+    }
+
+    /// The `k` haystack windows closest to `needle` by DTW distance
+    ///
+    /// Unlike `find_similar_generic` there is no threshold: the best `k`
+    /// windows are returned, nearest first, with ties going to the earlier
+    /// window. Only the current best `k` are kept, and once `k` windows have
+    /// been seen, a window's DTW is abandoned as soon as its
+    /// `IncrementalDtw::lower_bound` reaches the k-th best distance. Returns
+    /// `SequenceTooLong` if the needle exceeds `max_sequence_length`.
+    /// Results are not cached.
+    pub fn find_top_k(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        k: usize,
+    ) -> Result<Vec<SimilarityMatch>, TemporalError> {
+        self.find_top_k_impl(haystack, needle, k, None)
+    }
+
+    /// Cancellable variant of `find_top_k`
+    ///
+    /// `cancel` is checked before every window; once it is set the scan stops
+    /// and `TemporalError::Cancelled` is returned.
+    pub fn find_top_k_cancellable(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        k: usize,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<SimilarityMatch>, TemporalError> {
+        self.find_top_k_impl(haystack, needle, k, Some(cancel))
+    }
+
+    fn find_top_k_impl(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        k: usize,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Vec<SimilarityMatch>, TemporalError> {
+        let needle_len = needle.len();
+        if needle_len > self.max_sequence_length {
+            return Err(TemporalError::SequenceTooLong(needle_len));
+        }
+        if k == 0 || needle_len == 0 || haystack.len() < needle_len {
+            return Ok(Vec::new());
+        }
+
+        let mut best: BinaryHeap<TopKEntry> = BinaryHeap::with_capacity(k + 1);
+        let mut dtw = IncrementalDtw::new(needle.to_vec());
+
+        'windows: for start_index in 0..=(haystack.len() - needle_len) {
+            check_cancelled(cancel)?;
+
+            let bound = if best.len() == k {
+                best.peek().map_or(f64::INFINITY, |worst| worst.distance)
+            } else {
+                f64::INFINITY
+            };
+
+            dtw.reset();
+            for item in &haystack[start_index..start_index + needle_len] {
+                dtw.push(item.clone());
+                if dtw.lower_bound() >= bound {
+                    continue 'windows;
+                }
+            }
+
+            best.push(TopKEntry {
+                distance: dtw.distance(),
+                start_index,
+            });
+            if best.len() > k {
+                best.pop();
+            }
+        }
+
+        Ok(best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|entry| {
+                SimilarityMatch::with_normalization(
+                    entry.start_index,
+                    entry.distance,
+                    self.normalization,
+                )
+            })
+            .collect())
+    }
+
+    /// Fraction of `target` explained by approximate copies of `source`
+    ///
+    /// Every window of `source` (`COVERAGE_WINDOW` elements, or the shorter
+    /// sequence's length) is slid over `target`; target windows whose DTW
+    /// distance per element is at most `threshold` are marked covered.
+    /// Returns the covered fraction of target elements, 0.0 for an empty
+    /// target or source.
+    pub fn coverage(&self, source: &[T], target: &[T], threshold: f64) -> f64 {
+        let window = COVERAGE_WINDOW.min(source.len()).min(target.len());
+        if window == 0 {
+            return 0.0;
+        }
+
+        let mut covered = vec![false; target.len()];
+        let mut seen = std::collections::HashSet::new();
+        for pattern in source.windows(window) {
+            if !seen.insert(pattern) {
+                continue;
+            }
+
+            let Ok(distances) = self.window_distances(target, pattern, None) else {
+                continue;
+            };
+            for (start, distance) in distances {
+                if distance / window as f64 <= threshold {
+                    covered[start..start + window].fill(true);
+                }
+            }
+        }
+
+        covered.iter().filter(|&&c| c).count() as f64 / target.len() as f64
+    }
+
+    /// Like `find_similar_generic`, with overlapping matches merged
+    ///
+    /// Matches whose windows overlap by more than `max_overlap` (a fraction
+    /// of the needle length, 0.0-1.0) are collapsed to the best-scoring one.
+    /// See [`merge_overlapping_matches`].
+    pub fn find_similar_merged(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        threshold: f64,
+        max_overlap: f64,
+    ) -> Result<Vec<SimilarityMatch>, TemporalError> {
+        let matches = self.find_similar_generic(haystack, needle, threshold)?;
+        Ok(merge_overlapping_matches(
+            matches,
+            needle.len(),
+            max_overlap,
+        ))
+    }
+
+    /// Count how many matches `find_similar_generic` would return per threshold
+    ///
+    /// Window distances are computed and sorted once, so sweeping many
+    /// thresholds costs a single scan of the haystack.
+    pub fn threshold_sweep(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        thresholds: &[f64],
+    ) -> Vec<(f64, usize)> {
+        if needle.is_empty() || haystack.len() < needle.len() {
+            return thresholds.iter().map(|&t| (t, 0)).collect();
+        }
+
+        let needle_len = needle.len() as f64;
+        let mut normalized: Vec<f64> = self
+            .window_distances(haystack, needle, None)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, distance)| distance / needle_len)
+            .collect();
+        normalized.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        thresholds
+            .iter()
+            .map(|&threshold| (threshold, normalized.partition_point(|&d| d <= threshold)))
+            .collect()
+    }
+
+    /// DTW distance between `needle` and every window of the haystack
+    fn window_distances(
+        &self,
+        haystack: &[T],
+        needle: &[T],
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Vec<(usize, f64)>, TemporalError> {
+        let needle_len = needle.len();
+        let mut distances = Vec::new();
+
+        if needle_len == 0 || haystack.len() < needle_len {
+            return Ok(distances);
+        }
+
+        let mut seq2 = Sequence::new();
+        for (i, item) in needle.iter().enumerate() {
+            seq2.push(item.clone(), i as u64);
+        }
 
         // Sliding window approach
         for start_idx in 0..=(haystack.len() - needle_len) {
+            check_cancelled(cancel)?;
+
             let window = &haystack[start_idx..start_idx + needle_len];
 
             // Convert to Sequence for comparison
@@ -505,43 +1334,66 @@ where
                 seq1.push(item.clone(), i as u64);
             }
 
-            let mut seq2 = Sequence::new();
-            for (i, item) in needle.iter().enumerate() {
-                seq2.push(item.clone(), i as u64);
-            }
-
-            // Compute DTW distance
             if let Ok(result) = self.dtw(&seq1, &seq2) {
-                // Normalize distance by pattern length
-                let normalized_distance = result.distance / needle_len as f64;
-
-                if normalized_distance <= threshold {
-                    matches.push(SimilarityMatch::new(start_idx, result.distance));
-                }
+                distances.push((start_idx, result.distance));
             }
         }
 
-        // Sort by distance (best matches first)
-        matches.sort_by(|a, b| {
-            a.distance
-                .partial_cmp(&b.distance)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        // Store in cache
-        if let Ok(mut cache) = self.similarity_cache.lock() {
-            cache.put(cache_key, matches.clone());
-        }
-
-        Ok(matches)
+        Ok(distances)
     }
 
     /// Detect recurring patterns in a sequence
+    ///
+    /// Overlapping occurrences all count, so "aaaa" has three occurrences
+    /// of "aa"; see `detect_recurring_patterns_with_overlap`.
     pub fn detect_recurring_patterns(
         &self,
         sequence: &[T],
         min_length: usize,
         max_length: usize,
+    ) -> Result<Vec<Pattern<T>>, TemporalError> {
+        self.detect_recurring_impl(sequence, min_length, max_length, true, None)
+    }
+
+    /// Detect recurring patterns, optionally counting only non-overlapping
+    /// occurrences
+    ///
+    /// With `allow_overlap` false, occurrences are taken greedily from the
+    /// left and each accepted one skips the next `pattern_len - 1` starts,
+    /// so "aaaa" has two occurrences of "aa" (at 0 and 2). `occurrences`,
+    /// frequency, and confidence all reflect the chosen mode.
+    pub fn detect_recurring_patterns_with_overlap(
+        &self,
+        sequence: &[T],
+        min_length: usize,
+        max_length: usize,
+        allow_overlap: bool,
+    ) -> Result<Vec<Pattern<T>>, TemporalError> {
+        self.detect_recurring_impl(sequence, min_length, max_length, allow_overlap, None)
+    }
+
+    /// Cancellable variant of `detect_recurring_patterns`
+    ///
+    /// `cancel` is checked before every window; once it is set the scan stops
+    /// and `TemporalError::Cancelled` is returned. Nothing is cached for a
+    /// cancelled scan.
+    pub fn detect_recurring_patterns_cancellable(
+        &self,
+        sequence: &[T],
+        min_length: usize,
+        max_length: usize,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<Pattern<T>>, TemporalError> {
+        self.detect_recurring_impl(sequence, min_length, max_length, true, Some(cancel))
+    }
+
+    fn detect_recurring_impl(
+        &self,
+        sequence: &[T],
+        min_length: usize,
+        max_length: usize,
+        allow_overlap: bool,
+        cancel: Option<&AtomicBool>,
     ) -> Result<Vec<Pattern<T>>, TemporalError> {
         if min_length > max_length {
             return Err(TemporalError::InvalidPatternLength(min_length, max_length));
@@ -553,10 +1405,12 @@ where
 
         // Generate cache key
         let cache_key = format!(
-            "patterns:{:?}:{}:{}",
+            "patterns:{:?}:{:016x}:{}:{}:{}",
             sequence.len(),
+            content_hash(&[sequence]),
             min_length,
-            max_length
+            max_length,
+            allow_overlap
         );
 
         // Check cache
@@ -569,17 +1423,10 @@ where
 
         self.record_cache_miss(&cache_key);
 
-        let mut pattern_map: HashMap<Vec<T>, Vec<usize>> = HashMap::new();
-
-        // Search for patterns of each length
-        for pattern_len in min_length..=max_length.min(sequence.len()) {
-            for start_idx in 0..=(sequence.len() - pattern_len) {
-                let pattern_seq = sequence[start_idx..start_idx + pattern_len].to_vec();
-
-                pattern_map
-                    .entry(pattern_seq)
-                    .or_insert_with(Vec::new)
-                    .push(start_idx);
+        let mut pattern_map = Self::window_occurrences(sequence, min_length, max_length, cancel)?;
+        if !allow_overlap {
+            for (pattern, occurrences) in pattern_map.iter_mut() {
+                *occurrences = non_overlapping(occurrences, pattern.len());
             }
         }
 
@@ -589,14 +1436,8 @@ where
             .filter(|(_, occurrences)| occurrences.len() >= 2)
             .map(|(seq, occurrences)| {
                 // Calculate confidence based on frequency and pattern length
-                let frequency = occurrences.len() as f64;
-                let pattern_len = seq.len() as f64;
-                let total_possible = (sequence.len() - seq.len() + 1) as f64;
-
-                // Confidence is weighted by frequency and pattern length
                 let confidence =
-                    ((frequency / total_possible) * (pattern_len / max_length as f64)).min(1.0);
-
+                    pattern_confidence(sequence.len(), seq.len(), occurrences.len(), max_length);
                 Pattern::new(seq, occurrences, confidence)
             })
             .collect();
@@ -617,84 +1458,1013 @@ where
 
         Ok(patterns)
     }
-}
-
-impl<T> Default for TemporalComparator<T>
-where
-    T: Clone + PartialEq + fmt::Debug + Serialize + Hash + Eq,
-{
-    fn default() -> Self {
-        Self::new(1000, 10000)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_sequence_creation() {
-        let mut seq: Sequence<i32> = Sequence::new();
-        seq.push(1, 100);
-        seq.push(2, 200);
+    /// Return the most significant recurring pattern
+    ///
+    /// Same ranking as the first entry of `detect_recurring_patterns`
+    /// (highest frequency, then highest confidence) without building and
+    /// sorting the full pattern list. Remaining ties go to the pattern that
+    /// occurs first.
+    pub fn dominant_pattern(
+        &self,
+        sequence: &[T],
+        min_length: usize,
+        max_length: usize,
+    ) -> Option<Pattern<T>> {
+        if min_length > max_length || sequence.len() < min_length {
+            return None;
+        }
 
-        assert_eq!(seq.len(), 2);
-        assert!(!seq.is_empty());
+        Self::window_occurrences(sequence, min_length, max_length, None)
+            .ok()?
+            .into_iter()
+            .filter(|(_, occurrences)| occurrences.len() >= 2)
+            .map(|(seq, occurrences)| {
+                let confidence =
+                    pattern_confidence(sequence.len(), seq.len(), occurrences.len(), max_length);
+                Pattern::new(seq, occurrences, confidence)
+            })
+            .max_by(|a, b| {
+                a.frequency()
+                    .cmp(&b.frequency())
+                    .then_with(|| {
+                        a.confidence
+                            .partial_cmp(&b.confidence)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .then_with(|| b.occurrences[0].cmp(&a.occurrences[0]))
+            })
     }
 
-    #[test]
-    fn test_dtw() {
-        let comparator = TemporalComparator::new(100, 1000);
+    /// Start indices of every window of `min_length..=max_length` elements
+    fn window_occurrences(
+        sequence: &[T],
+        min_length: usize,
+        max_length: usize,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<HashMap<Vec<T>, Vec<usize>>, TemporalError> {
+        let mut pattern_map: HashMap<Vec<T>, Vec<usize>> = HashMap::new();
 
-        let mut seq1: Sequence<i32> = Sequence::new();
-        seq1.push(1, 100);
-        seq1.push(2, 200);
-        seq1.push(3, 300);
+        // Search for patterns of each length
+        for pattern_len in min_length..=max_length.min(sequence.len()) {
+            for start_idx in 0..=(sequence.len() - pattern_len) {
+                check_cancelled(cancel)?;
 
-        let mut seq2: Sequence<i32> = Sequence::new();
-        seq2.push(1, 100);
-        seq2.push(2, 200);
-        seq2.push(3, 300);
+                let pattern_seq = sequence[start_idx..start_idx + pattern_len].to_vec();
 
-        let result = comparator
-            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
-            .unwrap();
-        assert_eq!(result.distance, 0.0);
-    }
+                pattern_map.entry(pattern_seq).or_default().push(start_idx);
+            }
+        }
 
-    #[test]
-    fn test_cache() {
-        let comparator = TemporalComparator::new(100, 1000);
+        Ok(pattern_map)
+    }
 
-        let mut seq1: Sequence<i32> = Sequence::new();
-        seq1.push(1, 1);
-        seq1.push(2, 2);
+    /// Detect subsequences that also occur reversed elsewhere in the sequence
+    ///
+    /// Each returned pattern holds the first-seen orientation; its occurrences
+    /// list the start indices of both forward and reversed matches. Palindromic
+    /// windows are their own reversal and are always reported. Windows shorter
+    /// than two elements are skipped since reversal is meaningless for them.
+    pub fn detect_reversed_patterns(
+        &self,
+        sequence: &[T],
+        min_length: usize,
+        max_length: usize,
+    ) -> Vec<Pattern<T>> {
+        let min_length = min_length.max(2);
+        if min_length > max_length || sequence.len() < min_length {
+            return Vec::new();
+        }
 
-        let mut seq2: Sequence<i32> = Sequence::new();
-        seq2.push(1, 1);
-        seq2.push(2, 2);
+        // pattern -> (forward occurrences, reversed occurrences)
+        let mut groups: HashMap<Vec<T>, (Vec<usize>, Vec<usize>)> = HashMap::new();
 
-        // First comparison - cache miss
-        comparator
-            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
-            .unwrap();
+        for pattern_len in min_length..=max_length.min(sequence.len()) {
+            for start_idx in 0..=(sequence.len() - pattern_len) {
+                let window = &sequence[start_idx..start_idx + pattern_len];
 
-        // Second comparison - cache hit
-        comparator
-            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
-            .unwrap();
+                if let Some((forward, _)) = groups.get_mut(window) {
+                    forward.push(start_idx);
+                    continue;
+                }
 
-        let stats = comparator.cache_stats();
-        assert_eq!(stats.hits, 1);
-        assert_eq!(stats.misses, 1);
-    }
+                let reversed: Vec<T> = window.iter().rev().cloned().collect();
+                if let Some((_, backward)) = groups.get_mut(&reversed) {
+                    backward.push(start_idx);
+                } else {
+                    groups.insert(window.to_vec(), (vec![start_idx], Vec::new()));
+                }
+            }
+        }
 
-    #[test]
-    fn test_find_similar_generic_integers() {
-        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+        let mut patterns: Vec<Pattern<T>> = groups
+            .into_iter()
+            .filter(|(seq, (_, backward))| !backward.is_empty() || seq.iter().eq(seq.iter().rev()))
+            .map(|(seq, (forward, backward))| {
+                let mut occurrences = forward;
+                occurrences.extend(backward);
+                occurrences.sort_unstable();
 
-        let haystack = vec![1, 2, 3, 4, 5, 3, 4, 5];
-        let needle = vec![3, 4, 5];
+                let confidence =
+                    pattern_confidence(sequence.len(), seq.len(), occurrences.len(), max_length);
+                Pattern::new(seq, occurrences, confidence)
+            })
+            .collect();
+
+        patterns.sort_by(|a, b| {
+            b.frequency().cmp(&a.frequency()).then_with(|| {
+                b.confidence
+                    .partial_cmp(&a.confidence)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+
+        patterns
+    }
+
+    /// Find the longest strictly monotonic run in a numeric sequence
+    ///
+    /// Returns `(start, end, direction)` with `end` inclusive. Equal
+    /// neighbours break a run; ties go to the earliest run.
+    pub fn longest_trend(&self, seq: &[f64]) -> (usize, usize, TrendDirection) {
+        let mut best = (0, 0, TrendDirection::Flat);
+        let mut run_start = 0;
+        let mut run_dir = TrendDirection::Flat;
+
+        for i in 1..seq.len() {
+            let dir = if seq[i] > seq[i - 1] {
+                TrendDirection::Increasing
+            } else if seq[i] < seq[i - 1] {
+                TrendDirection::Decreasing
+            } else {
+                TrendDirection::Flat
+            };
+
+            if dir != run_dir {
+                run_start = i - 1;
+                run_dir = dir;
+            }
+
+            if run_dir != TrendDirection::Flat && i - run_start > best.1 - best.0 {
+                best = (run_start, i, run_dir);
+            }
+        }
+
+        best
+    }
+
+    /// Smith-Waterman local alignment of two sequences
+    ///
+    /// Finds the pair of subranges whose alignment scores highest, where
+    /// equal elements add `match_score` and mismatches and gaps subtract
+    /// `mismatch_penalty` and `gap` respectively. Returns
+    /// `(range1, range2, score)`; both ranges are empty with a score of 0.0
+    /// when no pair of elements matches. Ties go to the earliest alignment end.
+    pub fn local_align(
+        &self,
+        seq1: &Sequence<T>,
+        seq2: &Sequence<T>,
+        match_score: f64,
+        mismatch_penalty: f64,
+        gap: f64,
+    ) -> (Range<usize>, Range<usize>, f64) {
+        let n = seq1.len();
+        let m = seq2.len();
+
+        let mut h = vec![vec![0.0_f64; m + 1]; n + 1];
+        let mut best = (0, 0, 0.0);
+
+        for i in 1..=n {
+            for j in 1..=m {
+                let substitution = if seq1.elements[i - 1].value == seq2.elements[j - 1].value {
+                    match_score
+                } else {
+                    -mismatch_penalty
+                };
+
+                h[i][j] = (h[i - 1][j - 1] + substitution)
+                    .max(h[i - 1][j] - gap)
+                    .max(h[i][j - 1] - gap)
+                    .max(0.0);
+
+                if h[i][j] > best.2 {
+                    best = (i, j, h[i][j]);
+                }
+            }
+        }
+
+        let (end1, end2, score) = best;
+        if score <= 0.0 {
+            return (0..0, 0..0, 0.0);
+        }
+
+        // Trace back from the best cell until the score drops to zero
+        let (mut i, mut j) = (end1, end2);
+        while i > 0 && j > 0 && h[i][j] > 0.0 {
+            let substitution = if seq1.elements[i - 1].value == seq2.elements[j - 1].value {
+                match_score
+            } else {
+                -mismatch_penalty
+            };
+
+            if h[i][j] == h[i - 1][j - 1] + substitution {
+                i -= 1;
+                j -= 1;
+            } else if h[i][j] == h[i - 1][j] - gap {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+
+        (i..end1, j..end2, score)
+    }
+
+    /// Sample entropy (SampEn) of a numeric sequence
+    ///
+    /// Counts pairs of length-`m` templates within Chebyshev distance `r` of
+    /// each other (`B`), and how many of those still match when extended to
+    /// `m + 1` elements (`A`); the result is `-ln(A / B)`. Self-matches are
+    /// excluded. Regular signals score near zero and noisy ones higher.
+    /// Returns `f64::INFINITY` when no templates match, since the statistic
+    /// is undefined there.
+    pub fn sample_entropy(&self, seq: &[f64], m: usize, r: f64) -> f64 {
+        if m == 0 || seq.len() <= m + 1 {
+            return f64::INFINITY;
+        }
+
+        // Templates start at 0..n so every one has an (m + 1)-th element
+        let n = seq.len() - m;
+        let mut b = 0u64;
+        let mut a = 0u64;
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let within_m = (0..m).all(|k| (seq[i + k] - seq[j + k]).abs() <= r);
+                if within_m {
+                    b += 1;
+                    if (seq[i + m] - seq[j + m]).abs() <= r {
+                        a += 1;
+                    }
+                }
+            }
+        }
+
+        if a == 0 || b == 0 {
+            return f64::INFINITY;
+        }
+
+        -(a as f64 / b as f64).ln()
+    }
+
+    /// Self-similarity baseline of a numeric sequence for `compare_normalized`
+    ///
+    /// The DTW distance (see [`numeric::dtw_distance`]) from `seq` to the
+    /// all-zero sequence, i.e. the sum of absolute values. It scales linearly
+    /// with the sequence, like DTW distances between sequences do.
+    pub fn self_similarity_baseline(&self, seq: &[f64]) -> f64 {
+        seq.iter().map(|x| x.abs()).sum()
+    }
+
+    /// Scale-invariant DTW similarity of two numeric sequences
+    ///
+    /// `1 - dtw(a, b) / sqrt(baseline(a) * baseline(b))`, clamped to
+    /// `[0, 1]`. Identical sequences score 1.0, and multiplying both
+    /// sequences by the same factor leaves the score unchanged. Sequences
+    /// with a zero baseline score 1.0 against an identical sequence and 0.0
+    /// otherwise.
+    pub fn compare_normalized(&self, a: &[f64], b: &[f64]) -> f64 {
+        let distance = numeric::dtw_distance(a, b);
+        if distance == 0.0 {
+            return 1.0;
+        }
+
+        let scale = (self.self_similarity_baseline(a) * self.self_similarity_baseline(b)).sqrt();
+        if scale == 0.0 || !scale.is_finite() {
+            return 0.0;
+        }
+
+        (1.0 - distance / scale).clamp(0.0, 1.0)
+    }
+
+    /// DTW matrix profile of a numeric series
+    ///
+    /// Entry `i` is the DTW distance (see [`numeric::dtw_distance`]) from the
+    /// subsequence `series[i..i + window]` to its nearest neighbor. Neighbors
+    /// starting fewer than `window / 2` (rounded up) positions away are
+    /// trivial matches and excluded. High values mark discords (anomalies),
+    /// low values motifs. Subsequences without a non-trivial neighbor get
+    /// `f64::INFINITY`; the profile is empty if `window` is zero or longer
+    /// than the series.
+    pub fn matrix_profile(&self, series: &[f64], window: usize) -> Vec<f64> {
+        if window == 0 || window > series.len() {
+            return Vec::new();
+        }
+
+        let count = series.len() - window + 1;
+        let exclusion = window.div_ceil(2);
+        let mut profile = vec![f64::INFINITY; count];
+
+        for i in 0..count {
+            for j in (i + exclusion)..count {
+                let distance =
+                    numeric::dtw_distance(&series[i..i + window], &series[j..j + window]);
+                profile[i] = profile[i].min(distance);
+                profile[j] = profile[j].min(distance);
+            }
+        }
+
+        profile
+    }
+
+    /// Start indices of the `k` most anomalous subsequences
+    ///
+    /// Discords are the highest finite matrix-profile entries, taken in
+    /// descending order; each pick excludes starts within `window` of it so
+    /// one anomaly is not reported several times.
+    pub fn top_discords(&self, series: &[f64], window: usize, k: usize) -> Vec<usize> {
+        let profile = self.matrix_profile(series, window);
+        select_profile_extremes(&profile, window, k, true)
+    }
+
+    /// Start indices of the `k` subsequences best repeated elsewhere
+    ///
+    /// Motifs are the lowest matrix-profile entries, taken in ascending
+    /// order with the same non-overlap exclusion as `top_discords`; both
+    /// members of a repeated pair are returned when they are far enough apart.
+    pub fn top_motifs(&self, series: &[f64], window: usize, k: usize) -> Vec<usize> {
+        let profile = self.matrix_profile(series, window);
+        select_profile_extremes(&profile, window, k, false)
+    }
+}
+
+/// LCS DP table for sequences of length `n` and `m`
+///
+/// `matches(i, j)` reports whether source element `i` equals target
+/// element `j`; cell `[i][j]` holds the LCS length of the prefixes.
+fn lcs_table(n: usize, m: usize, matches: impl Fn(usize, usize) -> bool) -> Vec<Vec<usize>> {
+    let mut dp = vec![vec![0; m + 1]; n + 1];
+
+    for i in 1..=n {
+        for j in 1..=m {
+            if matches(i - 1, j - 1) {
+                dp[i][j] = dp[i - 1][j - 1] + 1;
+            } else {
+                dp[i][j] = dp[i - 1][j].max(dp[i][j - 1]);
+            }
+        }
+    }
+
+    dp
+}
+
+/// Cost matrix storing only the cells within `band` of the diagonal
+///
+/// Cells outside the band read as infinity.
+struct BandedMatrix {
+    cells: Vec<f64>,
+    band: usize,
+    row_width: usize,
+    columns: usize,
+}
+
+impl BandedMatrix {
+    fn new(n: usize, m: usize, band: usize) -> Self {
+        let row_width = band.saturating_mul(2).saturating_add(1).min(m + 1);
+        Self {
+            cells: vec![f64::INFINITY; (n + 1) * row_width],
+            band,
+            row_width,
+            columns: m + 1,
+        }
+    }
+
+    /// Storage index of cell `(i, j)`, if it lies within the band
+    fn index(&self, i: usize, j: usize) -> Option<usize> {
+        // First stored column of row i, kept far enough left that the row
+        // never runs past column m
+        let first = i
+            .saturating_sub(self.band)
+            .min(self.columns - self.row_width);
+        let offset = j.checked_sub(first)?;
+        (j.abs_diff(i) <= self.band && offset < self.row_width)
+            .then_some(i * self.row_width + offset)
+    }
+
+    fn get(&self, i: usize, j: usize) -> f64 {
+        self.index(i, j)
+            .map_or(f64::INFINITY, |idx| self.cells[idx])
+    }
+
+    fn set(&mut self, i: usize, j: usize, value: f64) {
+        if let Some(idx) = self.index(i, j) {
+            self.cells[idx] = value;
+        }
+    }
+}
+
+/// Window length used by `TemporalComparator::coverage`
+pub const COVERAGE_WINDOW: usize = 4;
+
+/// Collapse similarity matches whose windows overlap beyond a fraction
+///
+/// Windows are `window_len` elements long. Matches are taken best first
+/// (lowest distance, then earliest start) and kept only if they overlap
+/// every kept match by at most `max_overlap * window_len` elements, so each
+/// cluster of near-duplicates is represented by its best match. The result
+/// is sorted by distance like `find_similar_generic`.
+pub fn merge_overlapping_matches(
+    mut matches: Vec<SimilarityMatch>,
+    window_len: usize,
+    max_overlap: f64,
+) -> Vec<SimilarityMatch> {
+    let allowed = max_overlap.clamp(0.0, 1.0) * window_len as f64;
+
+    matches.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.start_index.cmp(&b.start_index))
+    });
+
+    let mut kept: Vec<SimilarityMatch> = Vec::new();
+    for candidate in matches {
+        let overlaps = kept.iter().any(|m| {
+            let overlap = window_len.saturating_sub(m.start_index.abs_diff(candidate.start_index));
+            overlap as f64 > allowed
+        });
+        if !overlaps {
+            kept.push(candidate);
+        }
+    }
+
+    kept
+}
+
+/// Pick up to `k` finite profile entries, largest or smallest first, at
+/// least `window` apart; ties go to the earliest index
+fn select_profile_extremes(profile: &[f64], window: usize, k: usize, largest: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..profile.len())
+        .filter(|&i| profile[i].is_finite())
+        .collect();
+    order.sort_by(|&a, &b| {
+        let by_value = profile[a]
+            .partial_cmp(&profile[b])
+            .unwrap_or(std::cmp::Ordering::Equal);
+        if largest {
+            by_value.reverse()
+        } else {
+            by_value
+        }
+        .then(a.cmp(&b))
+    });
+
+    let mut picked: Vec<usize> = Vec::with_capacity(k);
+    for index in order {
+        if picked.len() == k {
+            break;
+        }
+        if picked.iter().all(|&p| p.abs_diff(index) >= window) {
+            picked.push(index);
+        }
+    }
+
+    picked
+}
+
+/// Candidate window in `find_top_k`, ordered by distance then position
+///
+/// The heap's maximum is the worst candidate kept so far.
+struct TopKEntry {
+    distance: f64,
+    start_index: usize,
+}
+
+impl PartialEq for TopKEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for TopKEntry {}
+
+impl PartialOrd for TopKEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopKEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.start_index.cmp(&other.start_index))
+    }
+}
+
+/// Pearson correlation of `a` and `b` over their common prefix
+///
+/// `None` if there are fewer than two pairs or either side has no variance.
+fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < 2 {
+        return None;
+    }
+    let (a, b) = (&a[..n], &b[..n]);
+
+    let mean_a = a.iter().sum::<f64>() / n as f64;
+    let mean_b = b.iter().sum::<f64>() / n as f64;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        let (dx, dy) = (x - mean_a, y - mean_b);
+        cov += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    }
+
+    if var_a == 0.0 || var_b == 0.0 {
+        return None;
+    }
+    Some((cov / (var_a * var_b).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Hash of the contents of `slices`, used in cache keys
+fn content_hash<T: Hash>(slices: &[&[T]]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    slices.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Greedily keep ascending `starts` that do not overlap the last kept one
+fn non_overlapping(starts: &[usize], pattern_len: usize) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::with_capacity(starts.len());
+    for &start in starts {
+        if kept.last().is_none_or(|&last| start >= last + pattern_len) {
+            kept.push(start);
+        }
+    }
+    kept
+}
+
+/// Return `TemporalError::Cancelled` if the cancel flag has been set
+fn check_cancelled(cancel: Option<&AtomicBool>) -> Result<(), TemporalError> {
+    match cancel {
+        Some(flag) if flag.load(AtomicOrdering::Relaxed) => Err(TemporalError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// Confidence of a pattern, weighted by its frequency and length
+fn pattern_confidence(
+    sequence_len: usize,
+    pattern_len: usize,
+    frequency: usize,
+    max_length: usize,
+) -> f64 {
+    let total_possible = (sequence_len - pattern_len + 1) as f64;
+    ((frequency as f64 / total_possible) * (pattern_len as f64 / max_length as f64)).min(1.0)
+}
+
+impl<T> Default for TemporalComparator<T>
+where
+    T: Clone + PartialEq + fmt::Debug + Serialize + Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new(1000, 10000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sequence of `values` with timestamps 0, 1, 2, ...
+    fn seq<T>(values: impl IntoIterator<Item = T>) -> Sequence<T> {
+        let mut sequence = Sequence::new();
+        for (i, value) in values.into_iter().enumerate() {
+            sequence.push(value, i as u64);
+        }
+        sequence
+    }
+
+    #[test]
+    fn test_sequence_creation() {
+        let mut seq: Sequence<i32> = Sequence::new();
+        seq.push(1, 100);
+        seq.push(2, 200);
+
+        assert_eq!(seq.len(), 2);
+        assert!(!seq.is_empty());
+    }
+
+    #[test]
+    fn test_sequence_is_monotonic() {
+        let mut seq: Sequence<i32> = Sequence::new();
+        assert!(seq.is_monotonic());
+
+        seq.push(1, 10);
+        seq.push(2, 10);
+        seq.push(3, 20);
+        assert!(seq.is_monotonic());
+
+        seq.push(4, 15);
+        assert!(!seq.is_monotonic());
+    }
+
+    #[test]
+    fn test_dtw() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        let mut seq1: Sequence<i32> = Sequence::new();
+        seq1.push(1, 100);
+        seq1.push(2, 200);
+        seq1.push(3, 300);
+
+        let mut seq2: Sequence<i32> = Sequence::new();
+        seq2.push(1, 100);
+        seq2.push(2, 200);
+        seq2.push(3, 300);
+
+        let result = comparator
+            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
+            .unwrap();
+        assert_eq!(result.distance, 0.0);
+    }
+
+    #[test]
+    fn test_dtw_banded() {
+        let comparator = TemporalComparator::new(100, 10_000);
+        let build = |values: &[i32]| {
+            let mut seq: Sequence<i32> = Sequence::new();
+            for (i, &v) in values.iter().enumerate() {
+                seq.push(v, i as u64);
+            }
+            seq
+        };
+
+        // Long near-diagonal pair: a wide band matches full DTW
+        let a: Vec<i32> = (0..500).map(|i| (i / 7) % 5).collect();
+        let b: Vec<i32> = (0..480).map(|i| ((i + 2) / 7) % 5).collect();
+        let (seq1, seq2) = (build(&a), build(&b));
+
+        let full = comparator.dtw(&seq1, &seq2).unwrap();
+        let wide = comparator.dtw_banded(&seq1, &seq2, 500).unwrap();
+        assert_eq!(wide.distance, full.distance);
+        assert_eq!(wide.alignment, full.alignment);
+        assert_eq!(wide.algorithm, ComparisonAlgorithm::DTWBanded(500));
+
+        // A narrow band is never better, and its path stays inside the band
+        // (widened to |n - m| = 20)
+        let narrow = comparator.dtw_banded(&seq1, &seq2, 5).unwrap();
+        assert!(narrow.distance >= full.distance);
+        assert!(narrow
+            .alignment
+            .unwrap()
+            .iter()
+            .all(|&(i, j)| i.abs_diff(j) <= 20));
+
+        // `compare` routes the variant to the same computation
+        let shifted = build(&[0, 0, 0, 1, 2, 3, 4, 5]);
+        let target = build(&[1, 2, 3, 4, 5, 0, 0, 0]);
+        let routed = comparator
+            .compare(&shifted, &target, ComparisonAlgorithm::DTWBanded(1))
+            .unwrap();
+        let direct = comparator.dtw_banded(&shifted, &target, 1).unwrap();
+        assert_eq!(routed.distance, direct.distance);
+        assert!(routed.distance > comparator.dtw(&shifted, &target).unwrap().distance);
+
+        let empty: Sequence<i32> = Sequence::new();
+        assert_eq!(
+            comparator.dtw_banded(&empty, &target, 2).unwrap().distance,
+            8.0
+        );
+    }
+
+    #[test]
+    fn test_compare_with_band_ratio() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        let mut seq1: Sequence<i32> = Sequence::new();
+        for (i, v) in [1, 1, 1, 1, 2, 3, 4, 5, 6, 7].into_iter().enumerate() {
+            seq1.push(v, i as u64);
+        }
+        let mut seq2: Sequence<i32> = Sequence::new();
+        for (i, v) in [1, 2, 3, 4, 5, 6, 7, 7, 7, 7].into_iter().enumerate() {
+            seq2.push(v, i as u64);
+        }
+
+        let full = comparator
+            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
+            .unwrap();
+        let unconstrained = comparator
+            .compare_with_band_ratio(&seq1, &seq2, ComparisonAlgorithm::DTW, 1.0)
+            .unwrap();
+        assert_eq!(unconstrained.distance, full.distance);
+        assert_eq!(unconstrained.alignment, full.alignment);
+        assert_eq!(full.distance, 0.0);
+
+        // A 10% band (1 cell) cannot absorb the 3-step shift
+        let banded = comparator
+            .compare_with_band_ratio(&seq1, &seq2, ComparisonAlgorithm::DTW, 0.1)
+            .unwrap();
+        assert!(banded.distance > full.distance);
+        assert!(banded
+            .alignment
+            .as_ref()
+            .unwrap()
+            .iter()
+            .all(|&(i, j)| i.abs_diff(j) <= 1));
+
+        let absolute = comparator.dtw_with_band(&seq1, &seq2, 1).unwrap();
+        assert_eq!(banded.distance, absolute.distance);
+        assert_eq!(banded.alignment, absolute.alignment);
+
+        for ratio in [0.0, -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                comparator.compare_with_band_ratio(&seq1, &seq2, ComparisonAlgorithm::DTW, ratio),
+                Err(TemporalError::InvalidBandRatio(_))
+            ));
+        }
+        assert!(comparator
+            .compare_with_band_ratio(&seq1, &seq2, ComparisonAlgorithm::LCS, 0.5)
+            .is_err());
+    }
+
+    #[test]
+    fn test_compare_time_aware() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        let mut seq1: Sequence<i32> = Sequence::new();
+        let mut seq2: Sequence<i32> = Sequence::new();
+        for (i, v) in [1, 2, 3, 4].into_iter().enumerate() {
+            seq1.push(v, i as u64 * 10);
+            seq2.push(v, i as u64 * 25);
+        }
+
+        let distances: Vec<f64> = [0.0, 0.1, 1.0]
+            .iter()
+            .map(|&weight| {
+                comparator
+                    .compare_time_aware(&seq1, &seq2, ComparisonAlgorithm::DTW, weight)
+                    .unwrap()
+                    .distance
+            })
+            .collect();
+
+        // Value-identical: no penalty without a time weight
+        assert_eq!(distances[0], 0.0);
+        assert!(distances[0] < distances[1]);
+        assert!(distances[1] < distances[2]);
+
+        // Identical timestamps are never penalized
+        let same = comparator
+            .compare_time_aware(&seq1, &seq1, ComparisonAlgorithm::DTW, 5.0)
+            .unwrap();
+        assert_eq!(same.distance, 0.0);
+
+        assert!(comparator
+            .compare_time_aware(&seq1, &seq2, ComparisonAlgorithm::LCS, 1.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_compare_with_metric() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        // Sensor readings in tenths: 3.0 vs 3.1 and 3.0 vs 99.0
+        let reference = seq([30, 30, 30]);
+        let near = seq([30, 31, 30]);
+        let far = seq([30, 990, 30]);
+
+        let distance = |other: &Sequence<i32>, metric| {
+            comparator
+                .compare_with_metric(&reference, other, ComparisonAlgorithm::DTW, metric)
+                .unwrap()
+                .distance
+        };
+
+        // Binary cost cannot tell the two apart and matches `compare`
+        assert_eq!(distance(&near, DistanceMetric::Hamming), 1.0);
+        assert_eq!(distance(&far, DistanceMetric::Hamming), 1.0);
+        assert_eq!(
+            comparator
+                .compare(&reference, &far, ComparisonAlgorithm::DTW)
+                .unwrap()
+                .distance,
+            1.0
+        );
+
+        assert_eq!(distance(&near, DistanceMetric::AbsoluteDiff), 1.0);
+        assert_eq!(distance(&far, DistanceMetric::AbsoluteDiff), 960.0);
+        assert_eq!(distance(&near, DistanceMetric::Squared), 1.0);
+        assert_eq!(distance(&far, DistanceMetric::Squared), 921_600.0);
+
+        let banded = comparator
+            .compare_with_metric(
+                &reference,
+                &near,
+                ComparisonAlgorithm::DTWBanded(1),
+                DistanceMetric::AbsoluteDiff,
+            )
+            .unwrap();
+        assert_eq!(banded.algorithm, ComparisonAlgorithm::DTWBanded(1));
+        assert_eq!(banded.distance, 1.0);
+
+        assert!(comparator
+            .compare_with_metric(
+                &reference,
+                &near,
+                ComparisonAlgorithm::LCS,
+                DistanceMetric::AbsoluteDiff
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_compare_correlation() {
+        let comparator = TemporalComparator::new(100, 1000);
+        let distance = |a: &[i32], b: &[i32]| {
+            comparator
+                .compare_numeric(
+                    &seq(a.iter().copied()),
+                    &seq(b.iter().copied()),
+                    ComparisonAlgorithm::Correlation,
+                )
+                .unwrap()
+                .distance
+        };
+
+        // Scale and offset do not matter, and anti-correlation counts too
+        assert!(distance(&[1, 2, 3, 4], &[10, 20, 30, 40]).abs() < 1e-12);
+        assert!(distance(&[1, 2, 3, 4], &[8, 6, 4, 2]).abs() < 1e-12);
+
+        let weak = distance(&[1, 2, 3, 4, 5], &[2, 1, 4, 3, 5]);
+        assert!(weak > 0.1 && weak < 1.0);
+
+        // Undefined correlation
+        assert_eq!(distance(&[1, 2, 3], &[5, 5, 5]), 1.0);
+        assert_eq!(distance(&[1], &[1]), 1.0);
+
+        // Other algorithms go through `compare`
+        let dtw = comparator
+            .compare_numeric(&seq([1, 2]), &seq([1, 3]), ComparisonAlgorithm::DTW)
+            .unwrap();
+        assert_eq!(dtw.distance, 1.0);
+
+        // The generic path cannot correlate arbitrary values
+        assert!(comparator
+            .compare(&seq([1, 2]), &seq([1, 2]), ComparisonAlgorithm::Correlation)
+            .is_err());
+    }
+
+    #[test]
+    fn test_mean_step_cost() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        let mut seq1: Sequence<i32> = Sequence::new();
+        for (i, v) in [1, 2, 3, 4, 5].into_iter().enumerate() {
+            seq1.push(v, i as u64);
+        }
+        let mut seq2: Sequence<i32> = Sequence::new();
+        for (i, v) in [1, 3, 3, 9].into_iter().enumerate() {
+            seq2.push(v, i as u64);
+        }
+
+        let result = comparator
+            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
+            .unwrap();
+        let path_len = result.alignment.as_ref().unwrap().len();
+        assert!(result.distance > 0.0);
+        assert_eq!(
+            result.mean_step_cost(),
+            Some(result.distance / path_len as f64)
+        );
+
+        let lcs = comparator
+            .compare(&seq1, &seq2, ComparisonAlgorithm::LCS)
+            .unwrap();
+        assert_eq!(lcs.mean_step_cost(), None);
+    }
+
+    #[test]
+    fn test_knn() {
+        let mut comparator = TemporalComparator::new(100, 1000);
+
+        comparator.add_sequence("exact".to_string(), seq([1, 2, 3, 4]));
+        comparator.add_sequence("far".to_string(), seq([9, 9, 9, 9]));
+        comparator.add_sequence("one_off".to_string(), seq([1, 2, 3, 5]));
+        comparator.add_sequence("two_off".to_string(), seq([1, 7, 3, 8]));
+        assert_eq!(comparator.corpus_len(), 4);
+
+        let query = seq([1, 2, 3, 4]);
+        let nearest = comparator.knn(&query, 3, ComparisonAlgorithm::DTW).unwrap();
+        assert_eq!(
+            nearest,
+            vec![
+                ("exact".to_string(), 0.0),
+                ("one_off".to_string(), 1.0),
+                ("two_off".to_string(), 2.0),
+            ]
+        );
+
+        // Replacing a stored sequence changes its rank
+        comparator.add_sequence("far".to_string(), seq([1, 2, 3, 4, 4]));
+        assert_eq!(comparator.corpus_len(), 4);
+        let ids: Vec<String> = comparator
+            .knn(&query, 2, ComparisonAlgorithm::DTW)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec!["exact".to_string(), "far".to_string()]);
+
+        assert_eq!(
+            comparator
+                .knn(&query, 10, ComparisonAlgorithm::DTW)
+                .unwrap()
+                .len(),
+            4
+        );
+        assert!(comparator
+            .knn(&query, 0, ComparisonAlgorithm::DTW)
+            .unwrap()
+            .is_empty());
+
+        // An over-long query is an error, not an empty result
+        let short = TemporalComparator::new(100, 3);
+        assert!(matches!(
+            short.knn(&query, 3, ComparisonAlgorithm::DTW),
+            Err(TemporalError::SequenceTooLong(4))
+        ));
+    }
+
+    #[test]
+    fn test_cache() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        let mut seq1: Sequence<i32> = Sequence::new();
+        seq1.push(1, 1);
+        seq1.push(2, 2);
+
+        let mut seq2: Sequence<i32> = Sequence::new();
+        seq2.push(1, 1);
+        seq2.push(2, 2);
+
+        // First comparison - cache miss
+        comparator
+            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
+            .unwrap();
+
+        // Second comparison - cache hit
+        comparator
+            .compare(&seq1, &seq2, ComparisonAlgorithm::DTW)
+            .unwrap();
+
+        let stats = comparator.cache_stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[test]
+    fn test_cache_keys_distinguish_equal_length_sequences() {
+        let comparator = TemporalComparator::new(100, 1000);
+
+        let same = comparator
+            .compare(&seq([1, 2, 3]), &seq([1, 2, 3]), ComparisonAlgorithm::DTW)
+            .unwrap();
+        let different = comparator
+            .compare(&seq([1, 2, 3]), &seq([7, 8, 9]), ComparisonAlgorithm::DTW)
+            .unwrap();
+        assert_eq!(same.distance, 0.0);
+        assert_eq!(different.distance, 3.0);
+        assert_eq!(comparator.cache_stats().hits, 0);
+
+        let matches = comparator
+            .find_similar_generic(&[1, 2, 3, 4], &[1, 2], 0.1)
+            .unwrap();
+        assert_eq!(matches[0].start_index, 0);
+        let matches = comparator
+            .find_similar_generic(&[1, 2, 3, 4], &[3, 4], 0.1)
+            .unwrap();
+        assert_eq!(matches[0].start_index, 2);
+
+        let patterns = comparator
+            .detect_recurring_patterns(&[1, 2, 1, 2, 5, 6], 2, 2)
+            .unwrap();
+        let other = comparator
+            .detect_recurring_patterns(&[3, 4, 3, 4, 5, 6], 2, 2)
+            .unwrap();
+        assert_eq!(patterns[0].sequence, vec![1, 2]);
+        assert_eq!(other[0].sequence, vec![3, 4]);
+        assert_eq!(comparator.cache_stats().hits, 0);
+    }
+
+    #[test]
+    fn test_find_similar_generic_integers() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        let haystack = vec![1, 2, 3, 4, 5, 3, 4, 5];
+        let needle = vec![3, 4, 5];
 
         let matches = comparator
             .find_similar_generic(&haystack, &needle, 0.1)
@@ -706,6 +2476,141 @@ mod tests {
         assert!(matches[0].similarity > 0.9); // High similarity for exact match
     }
 
+    #[test]
+    fn test_similarity_normalization() {
+        // Default keeps the historical exp(-d / 10) mapping
+        assert_eq!(SimilarityMatch::new(0, 5.0).similarity, (-0.5f64).exp());
+        assert_eq!(
+            SimilarityNormalization::default(),
+            SimilarityNormalization::Exponential { decay: 10.0 }
+        );
+
+        // A larger decay suits larger distances
+        let wide = SimilarityNormalization::Exponential { decay: 100.0 };
+        assert!(wide.similarity(20.0) > SimilarityMatch::new(0, 20.0).similarity);
+
+        let linear = SimilarityNormalization::Linear { max_distance: 8.0 };
+        assert_eq!(linear.similarity(0.0), 1.0);
+        assert_eq!(linear.similarity(2.0), 0.75);
+        assert_eq!(linear.similarity(8.0), 0.0);
+        assert_eq!(linear.similarity(20.0), 0.0);
+
+        // Degenerate scales only accept exact matches
+        for degenerate in [
+            SimilarityNormalization::Exponential { decay: 0.0 },
+            SimilarityNormalization::Exponential { decay: -2.0 },
+            SimilarityNormalization::Linear { max_distance: 0.0 },
+        ] {
+            assert_eq!(degenerate.similarity(0.0), 1.0, "{:?}", degenerate);
+            assert_eq!(degenerate.similarity(3.0), 0.0, "{:?}", degenerate);
+        }
+
+        let sigmoid = SimilarityNormalization::Sigmoid {
+            midpoint: 4.0,
+            steepness: 1.0,
+        };
+        assert_eq!(sigmoid.similarity(4.0), 0.5);
+        assert!(sigmoid.similarity(0.0) > 0.95);
+        assert!(sigmoid.similarity(8.0) < 0.05);
+
+        // The comparator applies its normalization to search results
+        let comparator = TemporalComparator::new(100, 1000).with_normalization(linear);
+        let matches = comparator
+            .find_similar_generic(&[1, 2, 3, 9, 9, 9], &[1, 2, 4], 1.0)
+            .unwrap();
+        assert_eq!(matches[0].start_index, 0);
+        assert_eq!(
+            matches[0].similarity,
+            linear.similarity(matches[0].distance)
+        );
+        assert_eq!(matches[0].similarity, 0.875);
+    }
+
+    #[test]
+    fn test_find_top_k() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        // Pseudo-random haystack with an exact and a near copy of the needle
+        let mut haystack: Vec<i32> = (0..400u32).map(|i| ((i * 7919) % 13) as i32).collect();
+        let needle = vec![20, 21, 22, 23, 24];
+        haystack.splice(100..105, needle.iter().copied());
+        haystack.splice(300..305, [20, 21, 99, 23, 24]);
+
+        let top = comparator.find_top_k(&haystack, &needle, 3).unwrap();
+        assert_eq!(top.len(), 3);
+        assert_eq!(top[0].start_index, 100);
+        assert_eq!(top[0].distance, 0.0);
+        assert_eq!(top[1].start_index, 300);
+        assert_eq!(top[1].distance, 1.0);
+
+        // Same windows and distances as sorting every window's full DTW
+        let mut all = comparator
+            .window_distances(&haystack, &needle, None)
+            .unwrap();
+        all.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        for k in [1, 5, 40] {
+            let top: Vec<(usize, f64)> = comparator
+                .find_top_k(&haystack, &needle, k)
+                .unwrap()
+                .into_iter()
+                .map(|m| (m.start_index, m.distance))
+                .collect();
+            assert_eq!(top, all[..k]);
+        }
+
+        assert!(comparator
+            .find_top_k(&haystack, &needle, 0)
+            .unwrap()
+            .is_empty());
+        assert!(comparator
+            .find_top_k(&needle, &haystack, 3)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            comparator.find_top_k(&needle, &needle, 10).unwrap().len(),
+            1
+        );
+
+        // Same limits as the other searches
+        let short: TemporalComparator<i32> = TemporalComparator::new(100, 4);
+        assert!(matches!(
+            short.find_top_k(&haystack, &needle, 3),
+            Err(TemporalError::SequenceTooLong(5))
+        ));
+        let cancel = AtomicBool::new(true);
+        assert!(matches!(
+            comparator.find_top_k_cancellable(&haystack, &needle, 3, &cancel),
+            Err(TemporalError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_find_similar_merged() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        // One match region around index 3, a distinct one at 12
+        let haystack = vec![9, 9, 9, 1, 2, 3, 4, 9, 9, 9, 9, 9, 1, 2, 3, 4, 9];
+        let needle = vec![1, 2, 3, 4];
+
+        let raw = comparator
+            .find_similar_generic(&haystack, &needle, 0.5)
+            .unwrap();
+        assert!(raw.len() > 2);
+
+        let merged = comparator
+            .find_similar_merged(&haystack, &needle, 0.5, 0.5)
+            .unwrap();
+        let starts: Vec<usize> = merged.iter().map(|m| m.start_index).collect();
+        assert_eq!(starts, vec![3, 12]);
+        assert_eq!(merged[0].distance, 0.0);
+
+        // Allowing full overlap keeps everything
+        assert_eq!(
+            merge_overlapping_matches(raw.clone(), needle.len(), 1.0).len(),
+            raw.len()
+        );
+    }
+
     #[test]
     fn test_detect_recurring_patterns_simple() {
         let comparator: TemporalComparator<char> = TemporalComparator::new(100, 1000);
@@ -725,4 +2630,509 @@ mod tests {
         assert_eq!(pattern.frequency(), 3);
         assert!(pattern.confidence > 0.0);
     }
+
+    #[test]
+    fn test_find_similar_cancelled_mid_scan() {
+        use std::time::{Duration, Instant};
+
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+        let haystack: Vec<i32> = (0..200_000).map(|i| i % 97).collect();
+        let needle: Vec<i32> = (0..64).collect();
+        let cancel = AtomicBool::new(false);
+
+        let start = Instant::now();
+        let result = std::thread::scope(|s| {
+            s.spawn(|| {
+                std::thread::sleep(Duration::from_millis(20));
+                cancel.store(true, AtomicOrdering::Relaxed);
+            });
+            comparator.find_similar_generic_cancellable(&haystack, &needle, 1.0, &cancel)
+        });
+
+        assert!(matches!(result, Err(TemporalError::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_detect_recurring_patterns_without_overlap() {
+        let comparator: TemporalComparator<char> = TemporalComparator::new(100, 1000);
+        let sequence: Vec<char> = "aaaa".chars().collect();
+
+        let overlapping = comparator
+            .detect_recurring_patterns(&sequence, 2, 2)
+            .unwrap();
+        assert_eq!(overlapping[0].occurrences, vec![0, 1, 2]);
+
+        let disjoint = comparator
+            .detect_recurring_patterns_with_overlap(&sequence, 2, 2, false)
+            .unwrap();
+        assert_eq!(disjoint.len(), 1);
+        assert_eq!(disjoint[0].occurrences, vec![0, 2]);
+        assert_eq!(disjoint[0].frequency(), 2);
+        assert!(disjoint[0].confidence < overlapping[0].confidence);
+
+        // Patterns left with a single occurrence are dropped
+        let sequence: Vec<char> = "aaab".chars().collect();
+        assert!(comparator
+            .detect_recurring_patterns_with_overlap(&sequence, 2, 2, false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            comparator
+                .detect_recurring_patterns_with_overlap(&sequence, 2, 2, true)
+                .unwrap()[0]
+                .occurrences,
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_detect_recurring_patterns_cancellable() {
+        let comparator: TemporalComparator<char> = TemporalComparator::new(100, 1000);
+        let sequence = vec!['a', 'b', 'c', 'a', 'b', 'c', 'a', 'b', 'c'];
+
+        let cancel = AtomicBool::new(true);
+        assert!(matches!(
+            comparator.detect_recurring_patterns_cancellable(&sequence, 2, 4, &cancel),
+            Err(TemporalError::Cancelled)
+        ));
+
+        cancel.store(false, AtomicOrdering::Relaxed);
+        let patterns = comparator
+            .detect_recurring_patterns_cancellable(&sequence, 2, 4, &cancel)
+            .unwrap();
+        let abc = patterns.iter().find(|p| p.sequence == vec!['a', 'b', 'c']);
+        assert_eq!(abc.map(Pattern::frequency), Some(3));
+    }
+
+    #[test]
+    fn test_local_align_common_middle() {
+        let comparator: TemporalComparator<char> = TemporalComparator::new(100, 1000);
+
+        let mut seq1: Sequence<char> = Sequence::new();
+        for (i, c) in "xxxxHELLOWORLDyy".chars().enumerate() {
+            seq1.push(c, i as u64);
+        }
+        let mut seq2: Sequence<char> = Sequence::new();
+        for (i, c) in "zzHELLOWORLDqqqqq".chars().enumerate() {
+            seq2.push(c, i as u64);
+        }
+
+        let (range1, range2, score) = comparator.local_align(&seq1, &seq2, 2.0, 1.0, 1.0);
+
+        assert_eq!(range1, 4..14);
+        assert_eq!(range2, 2..12);
+        assert_eq!(score, 20.0);
+
+        let (empty1, empty2, zero) = comparator.local_align(&seq1, &Sequence::new(), 2.0, 1.0, 1.0);
+        assert!(empty1.is_empty() && empty2.is_empty());
+        assert_eq!(zero, 0.0);
+    }
+
+    #[test]
+    fn test_sample_entropy_periodic_vs_noise() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        let periodic: Vec<f64> = (0..300)
+            .map(|i| (i as f64 * std::f64::consts::PI / 10.0).sin())
+            .collect();
+
+        // Deterministic LCG noise in [-1, 1)
+        let mut state: u64 = 42;
+        let noise: Vec<f64> = (0..300)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+            })
+            .collect();
+
+        let periodic_entropy = comparator.sample_entropy(&periodic, 2, 0.2);
+        let noise_entropy = comparator.sample_entropy(&noise, 2, 0.2);
+
+        assert!(periodic_entropy < 0.5, "periodic: {}", periodic_entropy);
+        assert!(noise_entropy > 1.0, "noise: {}", noise_entropy);
+
+        assert_eq!(
+            comparator.sample_entropy(&[1.0, 2.0], 2, 0.2),
+            f64::INFINITY
+        );
+    }
+
+    /// Sine wave with period 20 and a spike over `anomaly..anomaly + 5`
+    fn series_with_anomaly(len: usize, anomaly: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| {
+                let base = (i as f64 * std::f64::consts::PI / 10.0).sin();
+                if (anomaly..anomaly + 5).contains(&i) {
+                    base + 3.0
+                } else {
+                    base
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matrix_profile_anomaly_is_maximum() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+        let series = series_with_anomaly(120, 60);
+        let window = 10;
+
+        let profile = comparator.matrix_profile(&series, window);
+        assert_eq!(profile.len(), series.len() - window + 1);
+
+        let (max_index, _) = profile
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert!((55..=60).contains(&max_index), "max at {}", max_index);
+
+        // Periodic stretches far from the spike have exact repeats
+        assert!(profile[10] < 1e-9);
+
+        assert!(comparator.matrix_profile(&series, 0).is_empty());
+        assert!(comparator.matrix_profile(&series[..5], 10).is_empty());
+    }
+
+    #[test]
+    fn test_top_discords_and_motifs() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        // Low-amplitude LCG noise with a motif at 20 and 80 and a spike at 50
+        let mut state: u64 = 7;
+        let mut series: Vec<f64> = (0..120)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                ((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * 0.3
+            })
+            .collect();
+        let motif = [0.0, 1.0, 2.0, 1.5, -1.0, -2.0, 0.5, 1.0, -0.5, 0.0];
+        series[20..30].copy_from_slice(&motif);
+        series[80..90].copy_from_slice(&motif);
+        for v in &mut series[50..55] {
+            *v += 5.0;
+        }
+
+        let discords = comparator.top_discords(&series, 10, 1);
+        assert_eq!(discords.len(), 1);
+        assert!(
+            (41..=54).contains(&discords[0]),
+            "discord at {}",
+            discords[0]
+        );
+
+        assert_eq!(comparator.top_motifs(&series, 10, 2), vec![20, 80]);
+
+        // Picks are at least a window apart
+        let many = comparator.top_discords(&series, 10, 5);
+        assert_eq!(many.len(), 5);
+        for (a, b) in many.iter().zip(many.iter().skip(1)) {
+            assert!(a.abs_diff(*b) >= 10);
+        }
+    }
+
+    #[test]
+    fn test_threshold_sweep_monotonic() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        let haystack = vec![1, 2, 3, 4, 5, 3, 4, 5, 9, 3, 4, 6];
+        let needle = vec![3, 4, 5];
+        let thresholds = [0.0, 0.2, 0.4, 0.7, 1.0];
+
+        let sweep = comparator.threshold_sweep(&haystack, &needle, &thresholds);
+
+        assert_eq!(sweep.len(), thresholds.len());
+        for window in sweep.windows(2) {
+            assert!(window[0].1 <= window[1].1);
+        }
+
+        // Counts agree with find_similar_generic
+        for &(threshold, count) in &sweep {
+            let matches = comparator
+                .find_similar_generic(&haystack, &needle, threshold)
+                .unwrap();
+            assert_eq!(matches.len(), count);
+        }
+        assert_eq!(sweep[0], (0.0, 2));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_round_trip() {
+        let mut seq: Sequence<i32> = Sequence::new();
+        for i in 0..1000 {
+            seq.push(i % 17, i as u64 * 10);
+        }
+
+        let bytes = seq.to_bincode().unwrap();
+        let decoded: Sequence<i32> = Sequence::from_bincode(&bytes).unwrap();
+        assert_eq!(decoded.elements, seq.elements);
+
+        let json = serde_json::to_vec(&seq).unwrap();
+        assert!(bytes.len() < json.len());
+
+        let pattern = Pattern::new(vec!['a', 'b', 'c'], vec![0, 3, 6], 0.75);
+        let decoded = Pattern::<char>::from_bincode(&pattern.to_bincode().unwrap()).unwrap();
+        assert_eq!(decoded.sequence, pattern.sequence);
+        assert_eq!(decoded.occurrences, pattern.occurrences);
+        assert_eq!(decoded.confidence, pattern.confidence);
+    }
+
+    #[test]
+    fn test_longest_trend() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(100, 1000);
+
+        // Rises over 0..=4, then falls over 4..=7
+        let series = [1.0, 2.0, 3.5, 4.0, 6.0, 5.0, 3.0, 1.0];
+        assert_eq!(
+            comparator.longest_trend(&series),
+            (0, 4, TrendDirection::Increasing)
+        );
+
+        // Longer fall than rise
+        let series = [1.0, 3.0, 2.0, 1.0, 0.5, 0.5, 0.2];
+        assert_eq!(
+            comparator.longest_trend(&series),
+            (1, 4, TrendDirection::Decreasing)
+        );
+
+        assert_eq!(
+            comparator.longest_trend(&[2.0, 2.0, 2.0]),
+            (0, 0, TrendDirection::Flat)
+        );
+        assert_eq!(comparator.longest_trend(&[]), (0, 0, TrendDirection::Flat));
+    }
+
+    #[test]
+    fn test_pinned_entry_survives_eviction() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(4, 1000);
+
+        let hot1 = seq(0..3);
+        let hot2 = seq(0..2);
+        let pinned = comparator
+            .pin(&hot1, &hot2, ComparisonAlgorithm::DTW)
+            .unwrap();
+        assert_eq!(comparator.pinned_count(), 1);
+
+        // Flood the LRU well past its capacity
+        for len in 10..30 {
+            let flood = seq(0..len);
+            comparator
+                .compare(&flood, &flood, ComparisonAlgorithm::DTW)
+                .unwrap();
+        }
+
+        // The earliest unpinned entry was evicted
+        let misses = comparator.cache_stats().misses;
+        let first = seq(0..10);
+        comparator
+            .compare(&first, &first, ComparisonAlgorithm::DTW)
+            .unwrap();
+        assert_eq!(comparator.cache_stats().misses, misses + 1);
+
+        // The pinned entry is still served from cache
+        let hits = comparator.cache_stats().hits;
+        let result = comparator
+            .compare(&hot1, &hot2, ComparisonAlgorithm::DTW)
+            .unwrap();
+        assert_eq!(result.distance, pinned.distance);
+        assert_eq!(comparator.cache_stats().hits, hits + 1);
+
+        assert!(comparator.unpin(&hot1, &hot2, ComparisonAlgorithm::DTW));
+        assert!(!comparator.unpin(&hot1, &hot2, ComparisonAlgorithm::DTW));
+        assert_eq!(comparator.pinned_count(), 0);
+    }
+
+    #[test]
+    fn test_detect_reversed_patterns() {
+        let comparator: TemporalComparator<char> = TemporalComparator::new(100, 1000);
+        let sequence: Vec<char> = "abcdecba".chars().collect();
+
+        let patterns = comparator.detect_reversed_patterns(&sequence, 3, 3);
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].sequence, vec!['a', 'b', 'c']);
+        assert_eq!(patterns[0].occurrences, vec![0, 5]);
+
+        // Palindromes are their own reversal
+        let sequence: Vec<char> = "xabay".chars().collect();
+        let patterns = comparator.detect_reversed_patterns(&sequence, 3, 3);
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].sequence, vec!['a', 'b', 'a']);
+        assert_eq!(patterns[0].occurrences, vec![1]);
+    }
+
+    #[test]
+    fn test_dominant_pattern() {
+        let comparator: TemporalComparator<char> = TemporalComparator::new(100, 1000);
+        let sequence: Vec<char> = "abcxabcyabczqrqr".chars().collect();
+
+        let dominant = comparator.dominant_pattern(&sequence, 2, 3).unwrap();
+        assert_eq!(dominant.sequence, vec!['a', 'b', 'c']);
+        assert_eq!(dominant.occurrences, vec![0, 4, 8]);
+
+        let all = comparator
+            .detect_recurring_patterns(&sequence, 2, 3)
+            .unwrap();
+        assert_eq!(all[0].frequency(), dominant.frequency());
+
+        let unique: Vec<char> = "abcdef".chars().collect();
+        assert!(comparator.dominant_pattern(&unique, 2, 3).is_none());
+    }
+
+    #[test]
+    fn test_compare_normalized() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(10, 1000);
+        let a = [1.0, 2.0, 3.0, 2.0, 1.0];
+        let b = [1.0, 2.5, 3.5, 2.0, 0.5, 1.0];
+
+        assert_eq!(comparator.self_similarity_baseline(&a), 9.0);
+        assert_eq!(comparator.compare_normalized(&a, &a), 1.0);
+
+        let score = comparator.compare_normalized(&a, &b);
+        assert!(score > 0.0 && score < 1.0);
+
+        // Scaling both sequences leaves the score unchanged
+        for factor in [0.01, 3.5, 1000.0] {
+            let scaled_a: Vec<f64> = a.iter().map(|x| x * factor).collect();
+            let scaled_b: Vec<f64> = b.iter().map(|x| x * factor).collect();
+            let scaled = comparator.compare_normalized(&scaled_a, &scaled_b);
+            assert!(
+                (scaled - score).abs() < 1e-9,
+                "factor {}: {} vs {}",
+                factor,
+                scaled,
+                score
+            );
+        }
+
+        assert_eq!(comparator.compare_normalized(&[0.0, 0.0], &[0.0, 0.0]), 1.0);
+        assert_eq!(comparator.compare_normalized(&[0.0, 0.0], &a), 0.0);
+    }
+
+    #[test]
+    fn test_compare_with_wildcards() {
+        let comparator: TemporalComparator<char> = TemporalComparator::new(10, 1000);
+        let is_wildcard = |c: &char| *c == '?';
+
+        let needle = seq("ab?d".chars());
+        for window in ["abxd", "abyd"] {
+            for algorithm in [
+                ComparisonAlgorithm::DTW,
+                ComparisonAlgorithm::LCS,
+                ComparisonAlgorithm::EditDistance,
+                ComparisonAlgorithm::Euclidean,
+            ] {
+                let result = comparator
+                    .compare_with_wildcards(&needle, &seq(window.chars()), algorithm, is_wildcard)
+                    .unwrap();
+                assert_eq!(result.distance, 0.0, "{:?} vs {}", algorithm, window);
+            }
+        }
+
+        // Non-wildcard positions still count, and plain compare is unaffected
+        let result = comparator
+            .compare_with_wildcards(
+                &needle,
+                &seq("zbxd".chars()),
+                ComparisonAlgorithm::EditDistance,
+                is_wildcard,
+            )
+            .unwrap();
+        assert_eq!(result.distance, 1.0);
+        let plain = comparator
+            .compare(
+                &needle,
+                &seq("abxd".chars()),
+                ComparisonAlgorithm::EditDistance,
+            )
+            .unwrap();
+        assert_eq!(plain.distance, 1.0);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let comparator: TemporalComparator<i32> = TemporalComparator::new(10, 1000);
+        let source: Vec<i32> = (0..20).collect();
+
+        // First half copied from the source, second half new material
+        let mut target: Vec<i32> = (5..15).collect();
+        target.extend(100..110);
+        assert_eq!(comparator.coverage(&source, &target, 0.0), 0.5);
+
+        // A small edit inside the copied half is tolerated by a looser threshold
+        target[4] = 99;
+        let strict = comparator.coverage(&source, &target, 0.0);
+        let loose = comparator.coverage(&source, &target, 0.25);
+        assert!(strict < 0.5);
+        assert!((loose - 0.5).abs() <= 0.1, "coverage {}", loose);
+
+        assert_eq!(comparator.coverage(&source, &source, 0.0), 1.0);
+        assert_eq!(comparator.coverage(&source, &[], 0.0), 0.0);
+        assert_eq!(comparator.coverage(&[], &target, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_lcs_sequence() {
+        let comparator = TemporalComparator::new(10, 100);
+        let (a, b) = (seq("ABCBDAB".chars()), seq("BDCABA".chars()));
+        let lcs = comparator.lcs_sequence(&a, &b).unwrap();
+        assert_eq!(lcs.iter().collect::<String>(), "BCBA");
+
+        // Length agrees with the LCS distance
+        let result = comparator
+            .compare(&a, &b, ComparisonAlgorithm::LCS)
+            .unwrap();
+        assert_eq!(result.distance, (a.len() + b.len() - 2 * lcs.len()) as f64);
+
+        // Ties prefer skipping elements of the first sequence
+        assert_eq!(
+            comparator
+                .lcs_sequence(&seq("XY".chars()), &seq("YX".chars()))
+                .unwrap(),
+            vec!['X']
+        );
+        assert_eq!(
+            comparator
+                .lcs_sequence(&seq("YX".chars()), &seq("XY".chars()))
+                .unwrap(),
+            vec!['Y']
+        );
+
+        let lcs_of =
+            |x: &Sequence<char>, y: &Sequence<char>| comparator.lcs_sequence(x, y).unwrap();
+        assert!(lcs_of(&seq("".chars()), &b).is_empty());
+        assert!(lcs_of(&a, &seq("".chars())).is_empty());
+        assert!(lcs_of(&a, &seq("xyz".chars())).is_empty());
+
+        // Same length limit as `compare`
+        let short = TemporalComparator::new(10, 6);
+        assert!(matches!(
+            short.lcs_sequence(&a, &b),
+            Err(TemporalError::SequenceTooLong(7))
+        ));
+    }
+
+    #[test]
+    fn test_edit_script() {
+        let source: Vec<char> = "kitten".chars().collect();
+        let target: Vec<char> = "sitting".chars().collect();
+        let steps = edit_script(&source, &target);
+
+        let edits = steps.iter().filter(|&&s| s != EditStep::Keep).count();
+        assert_eq!(edits, 3);
+        assert_eq!(steps[0], EditStep::Substitute);
+        assert_eq!(*steps.last().unwrap(), EditStep::Insert);
+
+        assert_eq!(
+            edit_script(&[1, 2, 3], &[1, 3]),
+            vec![EditStep::Keep, EditStep::Delete, EditStep::Keep,]
+        );
+        assert!(edit_script::<i32>(&[], &[]).is_empty());
+        assert_eq!(edit_script(&[], &[7, 8]), vec![EditStep::Insert; 2]);
+    }
 }
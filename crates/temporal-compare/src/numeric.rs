@@ -0,0 +1,129 @@
+//! DTW specialized for numeric `f64` sequences
+//!
+//! The generic comparator needs `T: Hash + Eq` and uses a 0/1 match cost, so
+//! it cannot handle floats. These functions use `|a - b|` as the local cost
+//! and keep only two matrix rows.
+//!
+//! With the `simd` feature, `dtw_distance_simd` sweeps the matrix by
+//! anti-diagonals, whose cells are independent, and produces bit-identical
+//! results to `dtw_distance` for NaN-free input.
+
+/// DTW distance between two `f64` sequences with absolute-difference cost
+///
+/// Returns `(a.len() + b.len())` if either sequence is empty, matching the
+/// generic comparator.
+pub fn dtw_distance(a: &[f64], b: &[f64]) -> f64 {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return (n + m) as f64;
+    }
+
+    let mut prev = vec![f64::INFINITY; m + 1];
+    let mut cur = vec![f64::INFINITY; m + 1];
+    prev[0] = 0.0;
+
+    for &ai in a {
+        cur[0] = f64::INFINITY;
+        for j in 0..m {
+            let cost = (ai - b[j]).abs();
+            cur[j + 1] = cost + prev[j].min(prev[j + 1]).min(cur[j]);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[m]
+}
+
+/// SIMD variant of [`dtw_distance`] with identical results
+///
+/// Cells on the same anti-diagonal (`i + j = k`) only depend on the two
+/// previous anti-diagonals, so each diagonal is computed four cells at a time.
+#[cfg(feature = "simd")]
+pub fn dtw_distance_simd(a: &[f64], b: &[f64]) -> f64 {
+    use wide::f64x4;
+
+    const LANES: usize = 4;
+
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return (n + m) as f64;
+    }
+
+    // With b reversed, b[k - i - 1] == b_rev[m - k + i] walks forward in i
+    let b_rev: Vec<f64> = b.iter().rev().copied().collect();
+
+    // Anti-diagonals k-2, k-1 and k of the (n+1) x (m+1) matrix, indexed by row
+    let mut d2 = vec![f64::INFINITY; n + 1];
+    let mut d1 = vec![f64::INFINITY; n + 1];
+    let mut d0 = vec![f64::INFINITY; n + 1];
+    d2[0] = 0.0;
+
+    for k in 2..=n + m {
+        let lo = k.saturating_sub(m).max(1);
+        let hi = n.min(k - 1);
+
+        let load = |v: &[f64], at: usize| f64x4::new(v[at..at + LANES].try_into().unwrap());
+
+        let mut i = lo;
+        while i + LANES <= hi + 1 {
+            let cost = (load(a, i - 1) - load(&b_rev, m + i - k)).abs();
+            let best = load(&d2, i - 1).min(load(&d1, i - 1)).min(load(&d1, i));
+            d0[i..i + LANES].copy_from_slice(&(cost + best).to_array());
+            i += LANES;
+        }
+        for i in i..=hi {
+            let cost = (a[i - 1] - b_rev[m + i - k]).abs();
+            d0[i] = cost + d2[i - 1].min(d1[i - 1]).min(d1[i]);
+        }
+
+        // Cells just outside this diagonal are boundary/out-of-range cells
+        d0[lo - 1] = f64::INFINITY;
+        if hi < n {
+            d0[hi + 1] = f64::INFINITY;
+        }
+
+        std::mem::swap(&mut d2, &mut d1);
+        std::mem::swap(&mut d1, &mut d0);
+    }
+
+    d1[n]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(len: usize, seed: u64) -> Vec<f64> {
+        (0..len as u64)
+            .map(|i| ((i * 7919 + seed * 104729) % 1000) as f64 / 37.0 - 13.5)
+            .collect()
+    }
+
+    #[test]
+    fn test_dtw_distance() {
+        assert_eq!(dtw_distance(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]), 0.0);
+        assert_eq!(dtw_distance(&[1.0, 2.0, 3.0], &[1.0, 2.0, 2.0, 3.0]), 0.0);
+        assert_eq!(dtw_distance(&[0.0, 0.0], &[1.0, 3.0]), 4.0);
+        assert_eq!(dtw_distance(&[], &[1.0, 2.0]), 2.0);
+
+        let (a, b) = (series(20, 1), series(13, 2));
+        assert_eq!(dtw_distance(&a, &b), dtw_distance(&b, &a));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_simd_matches_scalar() {
+        for (n, m) in [(1, 1), (3, 5), (8, 8), (17, 30), (64, 61), (129, 128)] {
+            let a = series(n, 1);
+            let b = series(m, 2);
+            assert_eq!(
+                dtw_distance_simd(&a, &b).to_bits(),
+                dtw_distance(&a, &b).to_bits(),
+                "n={} m={}",
+                n,
+                m
+            );
+        }
+        assert_eq!(dtw_distance_simd(&[], &[1.0]), 1.0);
+    }
+}
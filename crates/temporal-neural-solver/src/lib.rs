@@ -27,6 +27,9 @@ pub enum TemporalError {
 
     #[error("Invalid state: {0}")]
     InvalidState(String),
+
+    #[error("Unknown formula template: {0}")]
+    UnknownTemplate(String),
 }
 
 /// Temporal operators
@@ -48,10 +51,14 @@ pub enum TemporalOperator {
     Not,
     /// Implies
     Implies,
+    /// Finally within the next N states (F≤N)
+    FinallyWithin(usize),
+    /// Globally for the next N states (G≤N)
+    GloballyWithin(usize),
 }
 
 /// A temporal formula
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TemporalFormula {
     /// Atomic proposition
     Atom(String),
@@ -89,6 +96,28 @@ impl TemporalFormula {
         }
     }
 
+    /// Create a step-bounded Finally formula (F≤n φ)
+    ///
+    /// Holds if φ holds at the current state or one of the next `n` states.
+    /// Bounds count trace positions, not timestamps.
+    pub fn finally_within(formula: TemporalFormula, n: usize) -> Self {
+        TemporalFormula::Unary {
+            op: TemporalOperator::FinallyWithin(n),
+            formula: Box::new(formula),
+        }
+    }
+
+    /// Create a step-bounded Globally formula (G≤n φ)
+    ///
+    /// Holds if φ holds at the current state and each of the next `n` states
+    /// present in the trace. Bounds count trace positions, not timestamps.
+    pub fn globally_within(formula: TemporalFormula, n: usize) -> Self {
+        TemporalFormula::Unary {
+            op: TemporalOperator::GloballyWithin(n),
+            formula: Box::new(formula),
+        }
+    }
+
     /// Create a Next formula (X φ)
     pub fn next(formula: TemporalFormula) -> Self {
         TemporalFormula::Unary {
@@ -125,6 +154,7 @@ impl TemporalFormula {
     }
 
     /// Create a Not formula (¬φ)
+    #[allow(clippy::should_implement_trait)]
     pub fn not(formula: TemporalFormula) -> Self {
         TemporalFormula::Unary {
             op: TemporalOperator::Not,
@@ -132,10 +162,129 @@ impl TemporalFormula {
         }
     }
 
+    /// Create an Implies formula (φ → ψ)
+    pub fn implies(left: TemporalFormula, right: TemporalFormula) -> Self {
+        TemporalFormula::Binary {
+            op: TemporalOperator::Implies,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
     /// Create an atomic proposition
     pub fn atom(name: impl Into<String>) -> Self {
         TemporalFormula::Atom(name.into())
     }
+
+    /// Nesting depth of the formula tree (an atom has depth 1)
+    ///
+    /// Computed iteratively so arbitrarily deep formulas can be measured
+    /// without recursion.
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = vec![(self, 1)];
+
+        while let Some((formula, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            match formula {
+                TemporalFormula::Unary { formula, .. } => stack.push((formula, depth + 1)),
+                TemporalFormula::Binary { left, right, .. } => {
+                    stack.push((left, depth + 1));
+                    stack.push((right, depth + 1));
+                }
+                _ => {}
+            }
+        }
+
+        max_depth
+    }
+}
+
+/// Named formula templates whose atoms act as placeholders
+///
+/// ```
+/// use midstreamer_neural_solver::{FormulaLibrary, TemporalFormula};
+/// use std::collections::HashMap;
+///
+/// let mut library = FormulaLibrary::new();
+/// library.define(
+///     "responsiveness",
+///     TemporalFormula::globally(TemporalFormula::implies(
+///         TemporalFormula::atom("request"),
+///         TemporalFormula::finally(TemporalFormula::atom("response")),
+///     )),
+/// );
+///
+/// let bindings = HashMap::from([("request", "login"), ("response", "session")]);
+/// let formula = library.instantiate("responsiveness", &bindings).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormulaLibrary {
+    templates: HashMap<String, TemporalFormula>,
+}
+
+impl FormulaLibrary {
+    /// Create an empty library
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a template, replacing any existing one with the same name
+    pub fn define(&mut self, name: impl Into<String>, template: TemporalFormula) {
+        self.templates.insert(name.into(), template);
+    }
+
+    /// Look up a template by name
+    pub fn get(&self, name: &str) -> Option<&TemporalFormula> {
+        self.templates.get(name)
+    }
+
+    /// Number of templates
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Check if the library has no templates
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Copy the template `name` with its atoms renamed by `bindings`
+    ///
+    /// Atoms without a binding are kept as they are.
+    pub fn instantiate(
+        &self,
+        name: &str,
+        bindings: &HashMap<&str, &str>,
+    ) -> Result<TemporalFormula, TemporalError> {
+        let template = self
+            .templates
+            .get(name)
+            .ok_or_else(|| TemporalError::UnknownTemplate(name.to_string()))?;
+        Ok(substitute_atoms(template, bindings))
+    }
+}
+
+/// Rename the atoms of `formula` according to `bindings`
+fn substitute_atoms(formula: &TemporalFormula, bindings: &HashMap<&str, &str>) -> TemporalFormula {
+    match formula {
+        TemporalFormula::Atom(name) => TemporalFormula::Atom(
+            bindings
+                .get(name.as_str())
+                .map_or_else(|| name.clone(), |bound| bound.to_string()),
+        ),
+        TemporalFormula::Unary { op, formula } => TemporalFormula::Unary {
+            op: op.clone(),
+            formula: Box::new(substitute_atoms(formula, bindings)),
+        },
+        TemporalFormula::Binary { op, left, right } => TemporalFormula::Binary {
+            op: op.clone(),
+            left: Box::new(substitute_atoms(left, bindings)),
+            right: Box::new(substitute_atoms(right, bindings)),
+        },
+        TemporalFormula::True => TemporalFormula::True,
+        TemporalFormula::False => TemporalFormula::False,
+    }
 }
 
 /// A state in the temporal model
@@ -179,6 +328,25 @@ impl TemporalTrace {
         }
     }
 
+    /// Build a trace from `(timestamp, propositions)` pairs
+    ///
+    /// States get sequential ids starting at 0. As with `push`, only the last
+    /// `max_length` states are kept.
+    pub fn from_states(
+        states: impl IntoIterator<Item = (u64, HashMap<String, bool>)>,
+        max_length: usize,
+    ) -> Self {
+        let mut trace = Self::new(max_length);
+        for (id, (timestamp, propositions)) in states.into_iter().enumerate() {
+            trace.push(TemporalState {
+                id: id as u64,
+                propositions,
+                timestamp,
+            });
+        }
+        trace
+    }
+
     pub fn push(&mut self, state: TemporalState) {
         if self.states.len() >= self.max_length {
             self.states.pop_front();
@@ -197,6 +365,19 @@ impl TemporalTrace {
     pub fn get(&self, index: usize) -> Option<&TemporalState> {
         self.states.get(index)
     }
+
+    /// Check that timestamps never decrease along the trace
+    ///
+    /// Returns the index of the first state whose timestamp is earlier than
+    /// its predecessor's. Repeated timestamps are allowed.
+    pub fn check_monotonic(&self) -> Result<(), usize> {
+        match (1..self.states.len())
+            .find(|&i| self.states[i].timestamp < self.states[i - 1].timestamp)
+        {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Result of verification
@@ -206,6 +387,85 @@ pub struct VerificationResult {
     pub formula: String,
     pub counterexample: Option<Vec<u64>>,
     pub confidence: f64,
+    /// Satisfaction of every subformula at the verified position, in pre-order
+    #[serde(default)]
+    pub node_results: Vec<bool>,
+}
+
+impl VerificationResult {
+    /// Render the formula tree as a Graphviz DOT graph.
+    ///
+    /// Nodes are colored by whether their subformula held (green) or not (red).
+    /// `formula` must be the formula this result was produced from.
+    pub fn to_dot(&self, formula: &TemporalFormula) -> String {
+        let mut dot = String::from("digraph formula {\n    node [style=filled];\n");
+        let mut next_id = 0;
+        self.write_dot_node(formula, &mut next_id, &mut dot);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Write `formula` and its children, returning the id assigned to `formula`
+    fn write_dot_node(
+        &self,
+        formula: &TemporalFormula,
+        next_id: &mut usize,
+        dot: &mut String,
+    ) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+
+        let label = match formula {
+            TemporalFormula::Atom(name) => dot_escape(name),
+            TemporalFormula::Unary { op, .. } | TemporalFormula::Binary { op, .. } => {
+                format!("{:?}", op)
+            }
+            TemporalFormula::True => "True".to_string(),
+            TemporalFormula::False => "False".to_string(),
+        };
+
+        let color = match self.node_results.get(id) {
+            Some(true) => "palegreen",
+            Some(false) => "lightcoral",
+            None => "lightgray",
+        };
+
+        dot.push_str(&format!(
+            "    n{} [label=\"{}\", fillcolor=\"{}\"];\n",
+            id, label, color
+        ));
+
+        let children: Vec<&TemporalFormula> = match formula {
+            TemporalFormula::Unary { formula, .. } => vec![formula],
+            TemporalFormula::Binary { left, right, .. } => vec![left, right],
+            _ => Vec::new(),
+        };
+
+        for child in children {
+            let child_id = self.write_dot_node(child, next_id, dot);
+            dot.push_str(&format!("    n{} -> n{};\n", id, child_id));
+        }
+
+        id
+    }
+}
+
+/// Default maximum formula nesting depth accepted by `verify`
+pub const DEFAULT_MAX_FORMULA_DEPTH: usize = 256;
+
+/// Escape `text` for use inside a quoted DOT label
+fn dot_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 /// Temporal neural solver
@@ -214,6 +474,7 @@ pub struct TemporalNeuralSolver {
     #[allow(dead_code)]
     max_solving_time_ms: u64,
     verification_strictness: VerificationStrictness,
+    max_formula_depth: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -234,9 +495,18 @@ impl TemporalNeuralSolver {
             trace: TemporalTrace::new(max_trace_length),
             max_solving_time_ms,
             verification_strictness,
+            max_formula_depth: DEFAULT_MAX_FORMULA_DEPTH,
         }
     }
 
+    /// Builder: Set the maximum formula nesting depth accepted by `verify`
+    ///
+    /// Verification recurses over the formula tree, so this bounds stack use.
+    pub fn with_max_formula_depth(mut self, depth: usize) -> Self {
+        self.max_formula_depth = depth;
+        self
+    }
+
     /// Add a state to the trace
     pub fn add_state(&mut self, state: TemporalState) {
         self.trace.push(state);
@@ -248,8 +518,17 @@ impl TemporalNeuralSolver {
             return Err(TemporalError::InvalidState("Empty trace".to_string()));
         }
 
+        if formula.depth() > self.max_formula_depth {
+            return Err(TemporalError::ParseError(
+                "max formula depth exceeded".to_string(),
+            ));
+        }
+
         let satisfied = self.check_formula(formula, 0)?;
 
+        let mut node_results = Vec::new();
+        self.collect_node_results(formula, 0, &mut node_results)?;
+
         let formula_str = format!("{:?}", formula);
 
         Ok(VerificationResult {
@@ -261,9 +540,33 @@ impl TemporalNeuralSolver {
                 None
             },
             confidence: self.calculate_confidence(),
+            node_results,
         })
     }
 
+    /// Evaluate every subformula at `position`, in pre-order
+    fn collect_node_results(
+        &self,
+        formula: &TemporalFormula,
+        position: usize,
+        results: &mut Vec<bool>,
+    ) -> Result<(), TemporalError> {
+        results.push(self.check_formula(formula, position)?);
+
+        match formula {
+            TemporalFormula::Unary { formula, .. } => {
+                self.collect_node_results(formula, position, results)?;
+            }
+            TemporalFormula::Binary { left, right, .. } => {
+                self.collect_node_results(left, position, results)?;
+                self.collect_node_results(right, position, results)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     /// Check if formula holds at given position in trace
     fn check_formula(
         &self,
@@ -310,6 +613,30 @@ impl TemporalNeuralSolver {
                         }
                         Ok(false)
                     }
+                    TemporalOperator::FinallyWithin(n) => {
+                        // F≤n φ: φ holds at one of positions [i, i + n]
+                        let end = position
+                            .saturating_add(*n)
+                            .min(self.trace.len().saturating_sub(1));
+                        for i in position..=end {
+                            if self.check_formula(formula, i)? {
+                                return Ok(true);
+                            }
+                        }
+                        Ok(false)
+                    }
+                    TemporalOperator::GloballyWithin(n) => {
+                        // G≤n φ: φ holds at all positions [i, i + n]
+                        let end = position
+                            .saturating_add(*n)
+                            .min(self.trace.len().saturating_sub(1));
+                        for i in position..=end {
+                            if !self.check_formula(formula, i)? {
+                                return Ok(false);
+                            }
+                        }
+                        Ok(true)
+                    }
                     _ => Err(TemporalError::ParseError(format!(
                         "Invalid unary operator: {:?}",
                         op
@@ -389,6 +716,205 @@ impl Default for TemporalNeuralSolver {
     }
 }
 
+/// Verifier that decides a formula while the trace is still arriving
+///
+/// The formula is progressed through each pushed state: it is rewritten into
+/// what the rest of the trace must still satisfy, so a push costs time in the
+/// size of that residual formula rather than the trace length. A verdict is
+/// reported as soon as the residual becomes `True` or `False`: `G φ` fails at
+/// the first state violating φ and `F φ` holds at the first state satisfying
+/// φ, while satisfaction of `G φ` is never settled by a finite prefix.
+///
+/// States are also kept in a trace capped at `max_trace_length` (1000 by
+/// default) for [`finish`](Self::finish), and no longer recorded once the
+/// verdict is final.
+pub struct IncrementalVerifier {
+    formula: TemporalFormula,
+    /// What the states still to come must satisfy
+    residual: TemporalFormula,
+    solver: TemporalNeuralSolver,
+    verdict: Option<VerificationResult>,
+}
+
+impl IncrementalVerifier {
+    /// Create a verifier for `formula` with an empty trace
+    pub fn new(formula: TemporalFormula) -> Self {
+        Self {
+            residual: formula.clone(),
+            formula,
+            solver: TemporalNeuralSolver::default(),
+            verdict: None,
+        }
+    }
+
+    /// Builder: Keep at most `max_length` states for `finish`
+    pub fn with_max_trace_length(mut self, max_length: usize) -> Self {
+        self.solver.trace.max_length = max_length.max(1);
+        self
+    }
+
+    /// Append a state and return the verdict once it is definitive
+    ///
+    /// After a verdict is reached, later states are ignored and the same
+    /// verdict is returned.
+    pub fn push_state(&mut self, state: TemporalState) -> Option<VerificationResult> {
+        if self.verdict.is_some() {
+            return self.verdict.clone();
+        }
+
+        if self.formula.depth() <= self.solver.max_formula_depth {
+            self.residual = progress(&self.residual, &state);
+        }
+        let state_id = state.id;
+        self.solver.add_state(state);
+
+        let satisfied = match self.residual {
+            TemporalFormula::True => true,
+            TemporalFormula::False => false,
+            _ => return None,
+        };
+
+        // Subformula results describe the retained trace, computed once
+        let mut node_results = Vec::new();
+        self.solver
+            .collect_node_results(&self.formula, 0, &mut node_results)
+            .ok()?;
+
+        self.verdict = Some(VerificationResult {
+            satisfied,
+            formula: format!("{:?}", self.formula),
+            counterexample: (!satisfied).then(|| vec![state_id]),
+            confidence: self.solver.calculate_confidence(),
+            node_results,
+        });
+        self.verdict.clone()
+    }
+
+    /// Definitive verdict reached so far, if any
+    pub fn verdict(&self) -> Option<&VerificationResult> {
+        self.verdict.as_ref()
+    }
+
+    /// Verdict for the trace as it stands, treating it as complete
+    ///
+    /// Returns the definitive verdict if one was reached, otherwise falls
+    /// back to [`TemporalNeuralSolver::verify`] over the retained states.
+    pub fn finish(&self) -> Result<VerificationResult, TemporalError> {
+        match &self.verdict {
+            Some(verdict) => Ok(verdict.clone()),
+            None => self.solver.verify(&self.formula),
+        }
+    }
+
+    /// Number of states retained in the trace
+    pub fn trace_length(&self) -> usize {
+        self.solver.trace_length()
+    }
+}
+
+/// Progress `formula` through `state`: what the following states must satisfy
+///
+/// `G φ` becomes `φ' ∧ G φ`, `F φ` becomes `φ' ∨ F φ` and `φ U ψ` becomes
+/// `ψ' ∨ (φ' ∧ φ U ψ)`, where `φ'` is φ progressed through `state`. Bounded
+/// operators count down their window. Malformed operators are kept as they
+/// are and never settle.
+fn progress(formula: &TemporalFormula, state: &TemporalState) -> TemporalFormula {
+    match formula {
+        TemporalFormula::True | TemporalFormula::False => formula.clone(),
+        TemporalFormula::Atom(prop) => {
+            if state.get_proposition(prop) {
+                TemporalFormula::True
+            } else {
+                TemporalFormula::False
+            }
+        }
+
+        TemporalFormula::Unary { op, formula: inner } => {
+            let now = || progress(inner, state);
+            match op {
+                TemporalOperator::Not => negate(now()),
+                TemporalOperator::Next => (**inner).clone(),
+                TemporalOperator::Globally => conjoin(now(), formula.clone()),
+                TemporalOperator::Finally => disjoin(now(), formula.clone()),
+                TemporalOperator::FinallyWithin(0) | TemporalOperator::GloballyWithin(0) => now(),
+                TemporalOperator::FinallyWithin(n) => disjoin(
+                    now(),
+                    TemporalFormula::finally_within((**inner).clone(), n - 1),
+                ),
+                TemporalOperator::GloballyWithin(n) => conjoin(
+                    now(),
+                    TemporalFormula::globally_within((**inner).clone(), n - 1),
+                ),
+                _ => formula.clone(),
+            }
+        }
+
+        TemporalFormula::Binary { op, left, right } => match op {
+            TemporalOperator::And => conjoin(progress(left, state), progress(right, state)),
+            TemporalOperator::Or => disjoin(progress(left, state), progress(right, state)),
+            TemporalOperator::Implies => {
+                disjoin(negate(progress(left, state)), progress(right, state))
+            }
+            TemporalOperator::Until => disjoin(
+                progress(right, state),
+                conjoin(progress(left, state), formula.clone()),
+            ),
+            _ => formula.clone(),
+        },
+    }
+}
+
+/// ¬φ, folding constants and double negation
+fn negate(formula: TemporalFormula) -> TemporalFormula {
+    match formula {
+        TemporalFormula::True => TemporalFormula::False,
+        TemporalFormula::False => TemporalFormula::True,
+        TemporalFormula::Unary {
+            op: TemporalOperator::Not,
+            formula,
+        } => *formula,
+        other => TemporalFormula::not(other),
+    }
+}
+
+/// φ ∧ ψ, folding constants and repeated conjuncts so residuals stay small
+fn conjoin(left: TemporalFormula, right: TemporalFormula) -> TemporalFormula {
+    match (left, right) {
+        (TemporalFormula::False, _) | (_, TemporalFormula::False) => TemporalFormula::False,
+        (TemporalFormula::True, other) | (other, TemporalFormula::True) => other,
+        (left, right) if has_operand(&right, &left, &TemporalOperator::And) => right,
+        (left, right) if has_operand(&left, &right, &TemporalOperator::And) => left,
+        (left, right) => TemporalFormula::and(left, right),
+    }
+}
+
+/// φ ∨ ψ, folding constants and repeated disjuncts so residuals stay small
+fn disjoin(left: TemporalFormula, right: TemporalFormula) -> TemporalFormula {
+    match (left, right) {
+        (TemporalFormula::True, _) | (_, TemporalFormula::True) => TemporalFormula::True,
+        (TemporalFormula::False, other) | (other, TemporalFormula::False) => other,
+        (left, right) if has_operand(&right, &left, &TemporalOperator::Or) => right,
+        (left, right) if has_operand(&left, &right, &TemporalOperator::Or) => left,
+        (left, right) => TemporalFormula::or(left, right),
+    }
+}
+
+/// Whether `operand` is `formula` or one of its operands under a chain of `op`
+fn has_operand(
+    formula: &TemporalFormula,
+    operand: &TemporalFormula,
+    op: &TemporalOperator,
+) -> bool {
+    match formula {
+        TemporalFormula::Binary {
+            op: chain,
+            left,
+            right,
+        } if chain == op => has_operand(left, operand, op) || has_operand(right, operand, op),
+        _ => formula == operand,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +957,48 @@ mod tests {
         assert!(trace.get(0).is_some());
     }
 
+    #[test]
+    fn test_trace_check_monotonic() {
+        let mut trace = TemporalTrace::new(10);
+        assert_eq!(trace.check_monotonic(), Ok(()));
+
+        for (id, timestamp) in [(0, 100), (1, 200), (2, 200)] {
+            trace.push(TemporalState::new(id, timestamp));
+        }
+        assert_eq!(trace.check_monotonic(), Ok(()));
+
+        trace.push(TemporalState::new(3, 150));
+        trace.push(TemporalState::new(4, 50));
+        assert_eq!(trace.check_monotonic(), Err(3));
+    }
+
+    #[test]
+    fn test_trace_from_states() {
+        let states: Vec<(u64, HashMap<String, bool>)> = (0..5u64)
+            .map(|i| {
+                let props =
+                    HashMap::from([("safe".to_string(), true), ("goal".to_string(), i == 4)]);
+                (i * 100, props)
+            })
+            .collect();
+
+        let trace = TemporalTrace::from_states(states, 10);
+        assert_eq!(trace.len(), 5);
+        assert_eq!(trace.get(3).unwrap().id, 3);
+        assert_eq!(trace.get(3).unwrap().timestamp, 300);
+
+        let mut solver = TemporalNeuralSolver::default();
+        for state in trace.states {
+            solver.add_state(state);
+        }
+
+        let formula = TemporalFormula::and(
+            TemporalFormula::globally(TemporalFormula::atom("safe")),
+            TemporalFormula::finally(TemporalFormula::atom("goal")),
+        );
+        assert!(solver.verify(&formula).unwrap().satisfied);
+    }
+
     #[test]
     fn test_verification_atom() {
         let mut solver = TemporalNeuralSolver::default();
@@ -514,4 +1082,252 @@ mod tests {
 
         assert!(result.satisfied);
     }
+
+    #[test]
+    fn test_max_formula_depth() {
+        let mut solver = TemporalNeuralSolver::default();
+        let mut state = TemporalState::new(1, 100);
+        state.set_proposition("safe", true);
+        solver.add_state(state);
+
+        let mut formula = TemporalFormula::atom("safe");
+        for _ in 0..DEFAULT_MAX_FORMULA_DEPTH {
+            formula = TemporalFormula::not(formula);
+        }
+        assert_eq!(formula.depth(), DEFAULT_MAX_FORMULA_DEPTH + 1);
+
+        match solver.verify(&formula) {
+            Err(TemporalError::ParseError(msg)) => {
+                assert_eq!(msg, "max formula depth exceeded")
+            }
+            other => panic!("Expected depth error, got {:?}", other),
+        }
+
+        let solver = solver.with_max_formula_depth(DEFAULT_MAX_FORMULA_DEPTH + 1);
+        assert!(solver.verify(&formula).unwrap().satisfied);
+    }
+
+    #[test]
+    fn test_verification_to_dot() {
+        let mut solver = TemporalNeuralSolver::default();
+
+        let mut state = TemporalState::new(1, 100);
+        state.set_proposition("safe", true);
+        state.set_proposition("ready", false);
+        solver.add_state(state);
+
+        let formula = TemporalFormula::and(
+            TemporalFormula::atom("safe"),
+            TemporalFormula::atom("ready"),
+        );
+        let result = solver.verify(&formula).unwrap();
+        let dot = result.to_dot(&formula);
+
+        assert!(dot.starts_with("digraph formula {"));
+        assert!(dot.contains("label=\"And\", fillcolor=\"lightcoral\""));
+        assert!(dot.contains("label=\"safe\", fillcolor=\"palegreen\""));
+        assert!(dot.contains("label=\"ready\", fillcolor=\"lightcoral\""));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n0 -> n2;"));
+
+        // Quotes, backslashes and newlines in atom names are escaped
+        let odd = TemporalFormula::atom("a\"b\\c\nd");
+        let dot = result.to_dot(&odd);
+        assert!(dot.contains(r#"label="a\"b\\c\nd""#));
+    }
+
+    #[test]
+    fn test_verification_finally_within() {
+        let mut solver = TemporalNeuralSolver::default();
+
+        // "goal" becomes true at step 3
+        for i in 0..6 {
+            let mut state = TemporalState::new(i, i * 100);
+            state.set_proposition("goal", i == 3);
+            solver.add_state(state);
+        }
+
+        let goal = TemporalFormula::atom("goal");
+        let within = TemporalFormula::finally_within(goal.clone(), 3);
+        assert!(solver.verify(&within).unwrap().satisfied);
+
+        let too_soon = TemporalFormula::finally_within(goal, 2);
+        assert!(!solver.verify(&too_soon).unwrap().satisfied);
+    }
+
+    #[test]
+    fn test_verification_globally_within() {
+        let mut solver = TemporalNeuralSolver::default();
+
+        // "safe" holds for steps 0..=2, then fails
+        for i in 0..6 {
+            let mut state = TemporalState::new(i, i * 100);
+            state.set_proposition("safe", i < 3);
+            solver.add_state(state);
+        }
+
+        let safe = TemporalFormula::atom("safe");
+        let within = TemporalFormula::globally_within(safe.clone(), 2);
+        assert!(solver.verify(&within).unwrap().satisfied);
+
+        let beyond = TemporalFormula::globally_within(safe, 3);
+        assert!(!solver.verify(&beyond).unwrap().satisfied);
+    }
+
+    #[test]
+    fn test_incremental_globally_refuted_early() {
+        let mut verifier =
+            IncrementalVerifier::new(TemporalFormula::globally(TemporalFormula::atom("safe")));
+
+        for i in 0..3 {
+            let mut state = TemporalState::new(i, i * 100);
+            state.set_proposition("safe", true);
+            assert!(verifier.push_state(state).is_none());
+        }
+
+        let mut violation = TemporalState::new(3, 300);
+        violation.set_proposition("safe", false);
+        let result = verifier.push_state(violation).unwrap();
+        assert!(!result.satisfied);
+        assert_eq!(result.counterexample, Some(vec![3]));
+
+        // The verdict stays settled, and later states are not recorded
+        let mut state = TemporalState::new(4, 400);
+        state.set_proposition("safe", true);
+        assert!(!verifier.push_state(state).unwrap().satisfied);
+        assert_eq!(verifier.trace_length(), 4);
+    }
+
+    #[test]
+    fn test_incremental_residual_stays_bounded() {
+        let responsiveness = TemporalFormula::globally(TemporalFormula::implies(
+            TemporalFormula::atom("request"),
+            TemporalFormula::finally(TemporalFormula::atom("response")),
+        ));
+        let mut verifier = IncrementalVerifier::new(responsiveness).with_max_trace_length(50);
+
+        // Requests keep arriving without a response: one pending obligation
+        for i in 0..10_000 {
+            let mut state = TemporalState::new(i, i);
+            state.set_proposition("request", true);
+            assert!(verifier.push_state(state).is_none());
+            assert!(verifier.residual.depth() <= 5, "residual grew at {}", i);
+        }
+        assert_eq!(verifier.trace_length(), 50);
+
+        // Answered requests leave only the original obligation
+        let mut state = TemporalState::new(10_000, 10_000);
+        state.set_proposition("response", true);
+        assert!(verifier.push_state(state).is_none());
+        assert_eq!(verifier.residual, verifier.formula);
+
+        // Until settles both ways
+        let until = TemporalFormula::until(
+            TemporalFormula::atom("waiting"),
+            TemporalFormula::atom("served"),
+        );
+        let mut verifier = IncrementalVerifier::new(until);
+        let mut waiting = TemporalState::new(0, 0);
+        waiting.set_proposition("waiting", true);
+        assert!(verifier.push_state(waiting).is_none());
+        assert!(
+            !verifier
+                .push_state(TemporalState::new(1, 1))
+                .unwrap()
+                .satisfied
+        );
+    }
+
+    #[test]
+    fn test_incremental_finally_satisfied_early() {
+        let done = TemporalFormula::atom("done");
+        let mut verifier = IncrementalVerifier::new(TemporalFormula::finally(done.clone()));
+
+        for i in 0..3 {
+            assert!(verifier
+                .push_state(TemporalState::new(i, i * 100))
+                .is_none());
+        }
+        assert!(!verifier.finish().unwrap().satisfied);
+
+        let mut state = TemporalState::new(3, 300);
+        state.set_proposition("done", true);
+        let result = verifier.push_state(state).unwrap();
+        assert!(result.satisfied);
+        assert!(result.counterexample.is_none());
+        assert!(verifier.verdict().is_some());
+
+        // Bounded windows settle negatively once they have fully arrived
+        let mut bounded = IncrementalVerifier::new(TemporalFormula::finally_within(done, 1));
+        assert!(bounded.push_state(TemporalState::new(0, 0)).is_none());
+        assert!(
+            !bounded
+                .push_state(TemporalState::new(1, 100))
+                .unwrap()
+                .satisfied
+        );
+    }
+
+    #[test]
+    fn test_formula_library() {
+        let mut library = FormulaLibrary::new();
+        library.define(
+            "responsiveness",
+            TemporalFormula::globally(TemporalFormula::implies(
+                TemporalFormula::atom("request"),
+                TemporalFormula::finally(TemporalFormula::atom("response")),
+            )),
+        );
+        assert_eq!(library.len(), 1);
+
+        let login = library
+            .instantiate(
+                "responsiveness",
+                &HashMap::from([("request", "login"), ("response", "session")]),
+            )
+            .unwrap();
+        let query = library
+            .instantiate(
+                "responsiveness",
+                &HashMap::from([("request", "query"), ("response", "result")]),
+            )
+            .unwrap();
+
+        let mut solver = TemporalNeuralSolver::default();
+        for (id, props) in [
+            vec!["login", "query"],
+            vec!["session"],
+            vec!["login"],
+            vec!["session"],
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut state = TemporalState::new(id as u64, id as u64 * 10);
+            for prop in props {
+                state.set_proposition(prop, true);
+            }
+            solver.add_state(state);
+        }
+
+        // Logins are answered with sessions, but the query never gets a result
+        assert!(solver.verify(&login).unwrap().satisfied);
+        assert!(!solver.verify(&query).unwrap().satisfied);
+
+        // The template itself is left untouched
+        match library.get("responsiveness") {
+            Some(TemporalFormula::Unary { formula, .. }) => match formula.as_ref() {
+                TemporalFormula::Binary { left, .. } => {
+                    assert!(matches!(left.as_ref(), TemporalFormula::Atom(a) if a == "request"))
+                }
+                other => panic!("unexpected template body: {:?}", other),
+            },
+            other => panic!("unexpected template: {:?}", other),
+        }
+
+        assert!(matches!(
+            library.instantiate("missing", &HashMap::new()),
+            Err(TemporalError::UnknownTemplate(_))
+        ));
+    }
 }
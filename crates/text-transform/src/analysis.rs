@@ -0,0 +1,51 @@
+//! Instrumented transform output
+//!
+//! `analyze()` runs the same pipeline as `transform()` and additionally
+//! reports which rules fired, for tuning trigger vocabularies.
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Input words and the output they produced, as byte ranges
+/// (see `transform_with_spans`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// Bytes of the original text consumed in one step
+    pub input_range: Range<usize>,
+    /// Bytes of the transformed text produced for them
+    pub output_range: Range<usize>,
+}
+
+/// A contextual number trigger that fired (e.g. "line forty two" → "line 42")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriggerMatch {
+    /// Trigger word as emitted (e.g. "line")
+    pub trigger: String,
+    /// Index of the trigger word in the whitespace-split input
+    pub position: usize,
+    /// Digits the following number words were converted to
+    pub number: String,
+}
+
+/// Result of an instrumented transform run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransformAnalysis {
+    /// Transformed text, identical to `transform()` output
+    pub output: String,
+    /// Contextual number triggers that fired, in input order
+    pub contextual_triggers: Vec<TriggerMatch>,
+}
+
+impl TransformAnalysis {
+    /// Distinct trigger words that fired
+    pub fn triggers_used(&self) -> Vec<&str> {
+        let mut triggers: Vec<&str> = self
+            .contextual_triggers
+            .iter()
+            .map(|m| m.trigger.as_str())
+            .collect();
+        triggers.sort_unstable();
+        triggers.dedup();
+        triggers
+    }
+}
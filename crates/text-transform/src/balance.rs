@@ -0,0 +1,150 @@
+//! Delimiter balance check for transform output
+//!
+//! Dictation mistakes like a forgotten "close paren" or a stray "quote"
+//! leave unbalanced delimiters in the output. `check_balance()` reports them
+//! with byte positions so callers can warn before the text is injected.
+
+/// Whether a delimiter was left open or closed without being opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceIssueKind {
+    /// Opening delimiter with no matching close
+    Unclosed,
+    /// Closing delimiter with no matching open
+    Unopened,
+}
+
+/// An unbalanced delimiter in transform output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceIssue {
+    pub kind: BalanceIssueKind,
+    /// The offending delimiter character
+    pub delimiter: char,
+    /// Byte offset of the delimiter in the output
+    pub position: usize,
+}
+
+/// Report unbalanced quotes, parens, brackets and braces in `output`
+///
+/// Brackets must nest properly; quotes toggle open and closed. A single
+/// quote between two alphanumeric characters is an apostrophe ("don't")
+/// and is ignored. Issues are returned in position order.
+pub fn check_balance(output: &str) -> Vec<BalanceIssue> {
+    let mut issues = Vec::new();
+    let mut brackets: Vec<(char, usize)> = Vec::new();
+    // Position of each currently open quote kind
+    let mut quotes: [(char, Option<usize>); 3] = [('"', None), ('\'', None), ('`', None)];
+
+    let mut prev: Option<char> = None;
+    let mut chars = output.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+
+        match c {
+            '(' | '[' | '{' => brackets.push((c, pos)),
+            ')' | ']' | '}' => {
+                let opener = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if brackets.last().is_some_and(|&(open, _)| open == opener) {
+                    brackets.pop();
+                } else {
+                    issues.push(BalanceIssue {
+                        kind: BalanceIssueKind::Unopened,
+                        delimiter: c,
+                        position: pos,
+                    });
+                }
+            }
+            '\'' if prev.is_some_and(char::is_alphanumeric)
+                && next.is_some_and(char::is_alphanumeric) => {}
+            '"' | '\'' | '`' => {
+                if let Some((_, open)) = quotes.iter_mut().find(|(q, _)| *q == c) {
+                    *open = match open {
+                        Some(_) => None,
+                        None => Some(pos),
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        prev = Some(c);
+    }
+
+    let unclosed = brackets
+        .into_iter()
+        .chain(
+            quotes
+                .into_iter()
+                .filter_map(|(q, open)| open.map(|pos| (q, pos))),
+        )
+        .map(|(delimiter, position)| BalanceIssue {
+            kind: BalanceIssueKind::Unclosed,
+            delimiter,
+            position,
+        });
+    issues.extend(unclosed);
+    issues.sort_by_key(|issue| issue.position);
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform;
+
+    #[test]
+    fn test_unclosed_paren() {
+        let output = transform("open paren x");
+        assert_eq!(output, "(x");
+        assert_eq!(
+            check_balance(&output),
+            vec![BalanceIssue {
+                kind: BalanceIssueKind::Unclosed,
+                delimiter: '(',
+                position: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_balanced_output() {
+        assert!(check_balance("(x)").is_empty());
+        assert!(check_balance("f([a], {b: \"c\"}) don't").is_empty());
+    }
+
+    #[test]
+    fn test_unopened_and_mismatched() {
+        let issues = check_balance("x) \"y");
+        assert_eq!(
+            issues,
+            vec![
+                BalanceIssue {
+                    kind: BalanceIssueKind::Unopened,
+                    delimiter: ')',
+                    position: 1,
+                },
+                BalanceIssue {
+                    kind: BalanceIssueKind::Unclosed,
+                    delimiter: '"',
+                    position: 3,
+                },
+            ]
+        );
+
+        let kinds: Vec<_> = check_balance("[x)")
+            .into_iter()
+            .map(|issue| (issue.kind, issue.delimiter))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (BalanceIssueKind::Unclosed, '['),
+                (BalanceIssueKind::Unopened, ')'),
+            ]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Incremental edits for text that has already been injected
+//!
+//! IME-style integrations type the transform output into another
+//! application. When the dictation is revised, `transform_ops` returns the
+//! few deletions and insertions that turn the text typed last time into the
+//! new output, instead of retyping all of it.
+
+use midstreamer_edit_distance::{edit_script, EditStep};
+use serde::{Deserialize, Serialize};
+
+use crate::transform;
+
+/// A single edit to previously injected text
+///
+/// Offsets count characters (not bytes) in the text as it stands when the
+/// operation is applied; operations are applied in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditOp {
+    /// Remove `count` characters starting at `at`
+    Delete { at: usize, count: usize },
+    /// Insert `text` before the character at `at`
+    Insert { at: usize, text: String },
+}
+
+/// Minimal edits turning `prev` (the previously injected output) into
+/// `transform(text)`
+///
+/// Built from a Levenshtein edit script over characters; each run of
+/// changed characters becomes at most one `Delete` followed by one `Insert`.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::{transform_ops, EditOp};
+///
+/// let ops = transform_ops("Hello world,", "Hello world period");
+/// assert_eq!(
+///     ops,
+///     vec![
+///         EditOp::Delete { at: 11, count: 1 },
+///         EditOp::Insert { at: 11, text: ".".to_string() },
+///     ]
+/// );
+/// ```
+pub fn transform_ops(prev: &str, text: &str) -> Vec<EditOp> {
+    let output = transform(text);
+    let source: Vec<char> = prev.chars().collect();
+    let target: Vec<char> = output.chars().collect();
+
+    let mut ops = Vec::new();
+    let mut pos = 0;
+    let mut target_idx = 0;
+    let mut deleted = 0;
+    let mut inserted = String::new();
+
+    for step in edit_script(&source, &target) {
+        match step {
+            EditStep::Keep => {
+                flush_run(&mut ops, &mut pos, &mut deleted, &mut inserted);
+                pos += 1;
+                target_idx += 1;
+            }
+            EditStep::Substitute => {
+                deleted += 1;
+                inserted.push(target[target_idx]);
+                target_idx += 1;
+            }
+            EditStep::Delete => deleted += 1,
+            EditStep::Insert => {
+                inserted.push(target[target_idx]);
+                target_idx += 1;
+            }
+        }
+    }
+    flush_run(&mut ops, &mut pos, &mut deleted, &mut inserted);
+
+    ops
+}
+
+/// Apply `ops` to `text` in order
+pub fn apply_ops(text: &str, ops: &[EditOp]) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for op in ops {
+        match op {
+            EditOp::Delete { at, count } => {
+                let start = (*at).min(chars.len());
+                let end = (start + count).min(chars.len());
+                chars.drain(start..end);
+            }
+            EditOp::Insert { at, text } => {
+                let at = (*at).min(chars.len());
+                chars.splice(at..at, text.chars());
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Emit the pending run of changes at `pos` and move past the inserted text
+fn flush_run(ops: &mut Vec<EditOp>, pos: &mut usize, deleted: &mut usize, inserted: &mut String) {
+    if *deleted > 0 {
+        ops.push(EditOp::Delete {
+            at: *pos,
+            count: *deleted,
+        });
+        *deleted = 0;
+    }
+    if !inserted.is_empty() {
+        let text = std::mem::take(inserted);
+        let len = text.chars().count();
+        ops.push(EditOp::Insert { at: *pos, text });
+        *pos += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_correction_is_minimal() {
+        let prev = transform("open paren x plus sign y close paren comma");
+        assert_eq!(prev, "(x + y),");
+
+        let ops = transform_ops(&prev, "open paren x plus sign z close paren period");
+        assert_eq!(
+            ops,
+            vec![
+                EditOp::Delete { at: 5, count: 1 },
+                EditOp::Insert {
+                    at: 5,
+                    text: "z".to_string()
+                },
+                EditOp::Delete { at: 7, count: 1 },
+                EditOp::Insert {
+                    at: 7,
+                    text: ".".to_string()
+                },
+            ]
+        );
+        assert_eq!(apply_ops(&prev, &ops), "(x + z).");
+    }
+
+    #[test]
+    fn test_ops_round_trip() {
+        let cases = [
+            ("", "hello comma world"),
+            ("hello, world", ""),
+            ("hello, world", "hello comma world exclamation mark"),
+            ("naïve café", "naïve café period"),
+            ("x = 1", "x equals sign number forty two"),
+        ];
+
+        for (prev, text) in cases {
+            let ops = transform_ops(prev, text);
+            assert_eq!(
+                apply_ops(prev, &ops),
+                transform(text),
+                "{:?} → {:?}",
+                prev,
+                text
+            );
+        }
+
+        assert!(transform_ops("a, b", "a comma b").is_empty());
+    }
+}
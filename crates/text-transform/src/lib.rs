@@ -24,8 +24,16 @@
 //! let result = transformer.transform("arkon");  // → "archon" (if learned)
 //! ```
 
+mod analysis;
+mod balance;
+mod edit_ops;
+mod options;
+mod pipeline;
+mod recording;
 mod rules;
 mod spacing;
+mod streaming;
+mod transformer;
 
 #[cfg(feature = "pyo3")]
 mod python_bindings;
@@ -33,8 +41,29 @@ mod python_bindings;
 // Text Transform v3: Intelligent pattern matching
 pub mod v3;
 
+pub use analysis::{Span, TransformAnalysis, TriggerMatch};
+pub use balance::{check_balance, BalanceIssue, BalanceIssueKind};
+pub use edit_ops::{apply_ops, transform_ops, EditOp};
+pub use options::{
+    DateFormat, HeadingStyle, OutputFormat, PhoneFormat, TransformOptions, Unit, UnitPlacement,
+    UnitTable,
+};
+pub use pipeline::{TransformPipeline, TransformStage};
+pub use recording::{RecordingTransformer, TransformSession};
 pub use rules::TransformRule;
-use rules::{CONTEXTUAL_NUMBER_TRIGGERS, NUMBER_WORDS, STATIC_MAPPINGS};
+use rules::{
+    CONTEXTUAL_NUMBER_TRIGGERS, MONTH_NAMES, NATO_PHONETIC, NUMBER_WORDS, ORDINAL_WORDS, SNIPPETS,
+    SNIPPET_CURSOR, STATIC_MAPPINGS,
+};
+pub use spacing::{SpacingConfig, SpacingPolicy};
+#[cfg(feature = "stream")]
+pub use streaming::transform_stream;
+pub use streaming::{StreamingTransform, StreamingTransformer};
+pub use transformer::Transformer;
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::sync::Arc;
 
 /// Parse number words starting at `start_idx` and return (number_string, words_consumed)
 ///
@@ -44,16 +73,104 @@ use rules::{CONTEXTUAL_NUMBER_TRIGGERS, NUMBER_WORDS, STATIC_MAPPINGS};
 /// - Years: "nineteen fifty" → "1950", "twenty twenty five" → "2025"
 /// - Codes: "four oh four" → "404", "eighty eighty" → "8080"
 /// - Decades: "nineteen fifties" → "1950s"
+/// - Ordinals: "third" → "3rd", "twenty first" → "21st", "twentieth" → "20th"
 fn parse_number_words(words_lower: &[String], start_idx: usize) -> (String, usize) {
+    let ordinal = parse_ordinal_words(words_lower, start_idx);
+    if ordinal.1 > 0 {
+        return ordinal;
+    }
+    parse_cardinal_words(words_lower, start_idx)
+}
+
+/// Parse an ordinal starting at `start_idx` and return (ordinal_string, words_consumed)
+///
+/// Either a single ordinal word ("third", "twentieth") or a tens word
+/// followed by a ones ordinal ("twenty first").
+fn parse_ordinal_words(words_lower: &[String], start_idx: usize) -> (String, usize) {
+    match ordinal_value(words_lower, start_idx) {
+        Some((n, words_consumed)) => (format_ordinal(n), words_consumed),
+        None => (String::new(), 0),
+    }
+}
+
+/// Value of the ordinal starting at `start_idx` and the words it spans
+fn ordinal_value(words_lower: &[String], start_idx: usize) -> Option<(i32, usize)> {
+    let word = words_lower.get(start_idx)?;
+
+    // Compound: "twenty first" → 21
+    let next_ordinal = words_lower
+        .get(start_idx + 1)
+        .and_then(|next| ORDINAL_WORDS.get(next.as_str()));
+    if let (Some(&tens), Some(&ones)) = (NUMBER_WORDS.get(word.as_str()), next_ordinal) {
+        if (20..=90).contains(&tens) && tens % 10 == 0 && (1..=9).contains(&ones) {
+            return Some((tens + ones, 2));
+        }
+    }
+
+    ORDINAL_WORDS.get(word.as_str()).map(|&n| (n, 1))
+}
+
+/// Parse a spoken date starting at `start_idx` and return (date, words_consumed)
+///
+/// A month name, an ordinal day valid for that month, and an optional
+/// spoken year: "march fifteenth twenty twenty four" → "March 15, 2024".
+/// A month word without an ordinal day ("march forward", "may I") is no
+/// match. Only multi-word years from 1000 on count, so "march first two"
+/// keeps its "two".
+fn parse_date_words(
+    words_lower: &[String],
+    start_idx: usize,
+    format: DateFormat,
+) -> (String, usize) {
+    const DAYS_IN_MONTH: [i32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+    let Some(month) = words_lower
+        .get(start_idx)
+        .and_then(|word| MONTH_NAMES.iter().position(|name| name == word))
+    else {
+        return (String::new(), 0);
+    };
+    let Some((day, day_words)) = ordinal_value(words_lower, start_idx + 1)
+        .filter(|&(day, _)| (1..=DAYS_IN_MONTH[month]).contains(&day))
+    else {
+        return (String::new(), 0);
+    };
+
+    let year_idx = start_idx + 1 + day_words;
+    let (year, year_words) = match parse_cardinal_words(words_lower, year_idx) {
+        (year, words) if words >= 2 && year.parse::<u32>().is_ok_and(|y| y >= 1000) => {
+            (Some(year), words)
+        }
+        _ => (None, 0),
+    };
+
+    let date = format.format(month + 1, day, year.as_deref());
+    (date, 1 + day_words + year_words)
+}
+
+/// Digits plus English ordinal suffix: 1 → "1st", 12 → "12th", 23 → "23rd"
+fn format_ordinal(n: i32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Parse cardinal number words only (see `parse_number_words`)
+fn parse_cardinal_words(words_lower: &[String], start_idx: usize) -> (String, usize) {
     if start_idx >= words_lower.len() {
         return (String::new(), 0);
     }
 
     // Helper: Check number type
-    let is_teen = |n: i32| n >= 13 && n <= 19;
-    let is_decade = |n: i32| n >= 10 && n <= 90 && n % 10 == 0;
-    let is_tens = |n: i32| n >= 20 && n <= 90 && n % 10 == 0; // 20, 30, ..., 90
-    let is_ones = |n: i32| n >= 1 && n <= 9;
+    let is_teen = |n: i32| (13..=19).contains(&n);
+    let is_decade = |n: i32| (10..=90).contains(&n) && n % 10 == 0;
+    let is_tens = |n: i32| (20..=90).contains(&n) && n % 10 == 0; // 20, 30, ..., 90
+    let is_ones = |n: i32| (1..=9).contains(&n);
 
     // Try 3-word patterns first
     if start_idx + 2 < words_lower.len() {
@@ -126,6 +243,64 @@ fn parse_number_words(words_lower: &[String], start_idx: usize) -> (String, usiz
     (String::new(), 0)
 }
 
+/// Parse a spoken decimal starting at `start_idx` and return (decimal_string, words_consumed)
+///
+/// Number words, then "point", then one or more single-digit words:
+/// "three point one four" → "3.14", "zero point five" → "0.5". Returns no
+/// match unless a digit follows "point", so prose like "three point plan"
+/// is left alone.
+fn parse_decimal_words(words_lower: &[String], start_idx: usize) -> (String, usize) {
+    let (mut decimal, integer_words) = parse_cardinal_words(words_lower, start_idx);
+    let point_idx = start_idx + integer_words;
+    if integer_words == 0 || words_lower.get(point_idx).map(String::as_str) != Some("point") {
+        return (String::new(), 0);
+    }
+
+    let digits: String = words_lower[point_idx + 1..]
+        .iter()
+        .map_while(|word| NUMBER_WORDS.get(word.as_str()).filter(|&&d| d <= 9))
+        .map(|d| d.to_string())
+        .collect();
+    if digits.is_empty() {
+        return (String::new(), 0);
+    }
+
+    let consumed = integer_words + 1 + digits.len();
+    decimal.push('.');
+    decimal.push_str(&digits);
+    (decimal, consumed)
+}
+
+/// Parse number words, including spoken decimals when `decimals` is set
+///
+/// A leading "minus" or "negative" makes the number negative: "minus forty
+/// two" → "-42". Only used after number triggers, so prose "c minus d" is
+/// left to the operator rule.
+fn parse_number(words_lower: &[String], start_idx: usize, decimals: bool) -> (String, usize) {
+    if let Some("minus" | "negative") = words_lower.get(start_idx).map(String::as_str) {
+        let (magnitude, consumed) = if decimals {
+            match parse_decimal_words(words_lower, start_idx + 1) {
+                (_, 0) => parse_cardinal_words(words_lower, start_idx + 1),
+                decimal => decimal,
+            }
+        } else {
+            parse_cardinal_words(words_lower, start_idx + 1)
+        };
+        if consumed == 0 {
+            return (String::new(), 0);
+        }
+        return (format!("-{}", magnitude), 1 + consumed);
+    }
+
+    if decimals {
+        let decimal = parse_decimal_words(words_lower, start_idx);
+        if decimal.1 > 0 {
+            return decimal;
+        }
+    }
+    parse_number_words(words_lower, start_idx)
+}
+
 /// Transform text by replacing verbal punctuation with actual symbols.
 ///
 /// This uses simple O(1) lookup tables with context-aware spacing rules.
@@ -155,24 +330,457 @@ fn parse_number_words(words_lower: &[String], start_idx: usize) -> (String, usiz
 /// assert_eq!(transform("git commit hyphen m"), "git commit -m");
 /// ```
 pub fn transform(text: &str) -> String {
+    transform_with_config(text, &SpacingConfig::default())
+}
+
+/// Transform text like [`transform`], with explicit spacing conventions.
+///
+/// The default [`SpacingConfig`] gives exactly the output of `transform`.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::{transform_with_config, SpacingConfig};
+///
+/// let markdown = SpacingConfig::new().with_collapse_operator_spaces(false);
+/// assert_eq!(
+///     transform_with_config("git commit hyphen m", &markdown),
+///     "git commit - m"
+/// );
+/// ```
+pub fn transform_with_config(text: &str, cfg: &SpacingConfig) -> String {
+    transform_with_options(text, &TransformOptions::new().with_spacing_policy(*cfg))
+}
+
+/// Transform raw bytes, validating that they are UTF-8 first.
+///
+/// Returns [`v3::TransformError::InvalidUtf8`] instead of panicking when the
+/// input comes from an external source with malformed content.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::try_transform;
+///
+/// assert_eq!(try_transform(b"Stop period").unwrap(), "Stop.");
+/// assert!(try_transform(&[0x66, 0xff, 0x6f]).is_err());
+/// ```
+pub fn try_transform(bytes: &[u8]) -> Result<String, v3::TransformError> {
+    let text = std::str::from_utf8(bytes)?;
+    Ok(transform(text))
+}
+
+/// Transform text like [`transform`], with explicit [`TransformOptions`].
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::{transform_with_options, TransformOptions};
+///
+/// let options = TransformOptions::new().with_markdown_escape(true);
+/// assert_eq!(transform_with_options("a asterisk b", &options), "a \\* b");
+/// ```
+pub fn transform_with_options(text: &str, options: &TransformOptions) -> String {
+    run_transform(
+        text,
+        options,
+        &RuntimeRules::default(),
+        &mut QuoteState::default(),
+    )
+    .output
+}
+
+/// Transform text and report which contextual triggers fired.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::analyze;
+///
+/// let analysis = analyze("line forty two");
+/// assert_eq!(analysis.output, "line 42");
+/// assert_eq!(analysis.contextual_triggers[0].trigger, "line");
+/// ```
+pub fn analyze(text: &str) -> TransformAnalysis {
+    run_transform(
+        text,
+        &TransformOptions::default(),
+        &RuntimeRules::default(),
+        &mut QuoteState::default(),
+    )
+}
+
+/// Transform text and report which input bytes produced which output bytes.
+///
+/// Output is identical to `transform()`. Each span covers the input words
+/// consumed in one step (a single word, a multi-word pattern, an escape
+/// such as "literal comma", or a number trigger with its number words) and
+/// the output they produced, without surrounding spaces. Spans are in input
+/// order; words that produce nothing get an empty output range.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::transform_with_spans;
+///
+/// let (output, spans) = transform_with_spans("Hello question mark");
+/// assert_eq!(output, "Hello?");
+/// assert_eq!(spans[1].input_range, 6..19);
+/// assert_eq!(spans[1].output_range, 5..6);
+/// ```
+pub fn transform_with_spans(text: &str) -> (String, Vec<Span>) {
+    let options = TransformOptions::default();
     let words: Vec<&str> = text.split_whitespace().collect();
-    let mut result = String::with_capacity(text.len() + 20); // Pre-allocate with buffer
-    let mut i = 0;
-    let mut quote_state = QuoteState::default();
-    let mut last_rule_no_space_after = false;
-    let mut last_rule_is_opening = false;
+    let words_lower = lookup_words(&words, &options);
+    let mut state = ScanState {
+        result: String::with_capacity(text.len() + 20),
+        step_spans: Some(Vec::new()),
+        ..ScanState::default()
+    };
+
+    scan_words(
+        &words,
+        &words_lower,
+        words.len(),
+        &mut state,
+        &options,
+        &RuntimeRules::default(),
+        &mut TransformAnalysis::default(),
+    );
+
+    // Byte offset of each word in `text`
+    let word_ranges: Vec<Range<usize>> = words
+        .iter()
+        .map(|word| {
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            start..start + word.len()
+        })
+        .collect();
+
+    let spans = state
+        .step_spans
+        .take()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(step_words, output_range)| Span {
+            input_range: word_ranges[step_words.start].start..word_ranges[step_words.end - 1].end,
+            output_range,
+        })
+        .collect();
+
+    (state.result, spans)
+}
+
+/// Export the static rule table as JSON for external tools.
+///
+/// The output is an object keyed by spoken phrase, sorted by phrase, with
+/// each rule's replacement and spacing flags.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::export_rules_json;
+///
+/// let rules: serde_json::Value = serde_json::from_str(&export_rules_json()).unwrap();
+/// assert_eq!(rules["comma"]["replacement"], ",");
+/// ```
+pub fn export_rules_json() -> String {
+    let sorted: BTreeMap<&str, &TransformRule> = STATIC_MAPPINGS
+        .iter()
+        .map(|(&phrase, rule)| (phrase, rule))
+        .collect();
+    serde_json::to_string_pretty(&sorted).expect("rule table serialization cannot fail")
+}
+
+/// Check whether `words` could still grow into a longer multi-word rule.
+///
+/// Returns true if some static rule phrase has more words than `words` and
+/// starts with them, compared case-insensitively. A complete phrase with
+/// no longer extension ("period") returns false, so dictation UIs can
+/// commit it instead of waiting for more words.
+///
+/// # Examples
+/// ```
+/// use midstreamer_text_transform::is_rule_prefix;
+///
+/// assert!(is_rule_prefix(&["question"]));
+/// assert!(!is_rule_prefix(&["banana"]));
+/// ```
+pub fn is_rule_prefix(words: &[&str]) -> bool {
+    STATIC_MAPPINGS.keys().any(|phrase| {
+        let phrase_words: Vec<&str> = phrase.split(' ').collect();
+        phrase_words.len() > words.len()
+            && phrase_words
+                .iter()
+                .zip(words)
+                .all(|(rule_word, word)| rule_word.eq_ignore_ascii_case(word))
+    })
+}
+
+/// Shared transform loop behind `transform_with_options()` and `analyze()`
+///
+/// `quote_state` is the quote state at the start of `text` and is updated to
+/// the state at its end. `rules` are the runtime additions of a
+/// `Transformer` (empty for the free functions).
+fn run_transform(
+    text: &str,
+    options: &TransformOptions,
+    rules: &RuntimeRules,
+    quote_state: &mut QuoteState,
+) -> TransformAnalysis {
+    let mut analysis = TransformAnalysis::default();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut state = ScanState {
+        result: String::with_capacity(text.len() + 20), // Pre-allocate with buffer
+        quote_state: *quote_state,
+        ..ScanState::default()
+    };
+
+    // Build rule lookup keys once to avoid repeated allocations
+    let words_lower = lookup_words(&words, options);
+
+    scan_words(
+        &words,
+        &words_lower,
+        words.len(),
+        &mut state,
+        options,
+        rules,
+        &mut analysis,
+    );
+
+    *quote_state = state.quote_state;
+    let mut result = state.result;
+    if options.collapse_whitespace {
+        result = spacing::collapse_spaces(&result);
+    }
+
+    analysis.output = result;
+    analysis
+}
+
+/// Literal block opening at a loop step
+enum LiteralBlock {
+    /// Word range of the block contents; "end literal" follows it
+    Closed(Range<usize>),
+    /// Opened, but no "end literal" starts before the search limit
+    Unclosed,
+}
+
+/// Literal block starting at `i`, if "literal" or "begin literal" opens one
+///
+/// A block closes at the first following "end literal" whose "end" comes
+/// before `limit`; "literal" inside the block is passed through rather than
+/// nested. `unclosed_from` is the block start of an earlier failed search
+/// with the same `limit`: no later block can close either, so each word is
+/// searched at most once per loop.
+fn literal_block(
+    words_lower: &[String],
+    i: usize,
+    limit: usize,
+    unclosed_from: &mut usize,
+) -> Option<LiteralBlock> {
+    let start = match words_lower[i].as_str() {
+        "literal" => i + 1,
+        "begin" if words_lower.get(i + 1).is_some_and(|w| w == "literal") => i + 2,
+        _ => return None,
+    };
+    if start >= *unclosed_from {
+        return Some(LiteralBlock::Unclosed);
+    }
+
+    let search_end = limit.min(words_lower.len().saturating_sub(1));
+    match (start..search_end).find(|&j| words_lower[j] == "end" && words_lower[j + 1] == "literal")
+    {
+        Some(end) => Some(LiteralBlock::Closed(start..end)),
+        None => {
+            *unclosed_from = start;
+            Some(LiteralBlock::Unclosed)
+        }
+    }
+}
+
+/// Output and spacing/quote state carried across the transform loop
+#[derive(Default)]
+pub(crate) struct ScanState {
+    pub(crate) result: String,
+    quote_state: QuoteState,
+    last_rule_no_space_after: bool,
+    last_rule_is_opening: bool,
+    /// Word range → output byte range of each loop step, when requested
+    step_spans: Option<Vec<(Range<usize>, Range<usize>)>>,
+}
+
+/// What a loop step may change in `ScanState`, for undoing it
+struct StepSnapshot {
+    /// Output length without trailing spaces, and the number of those spaces
+    stable_len: usize,
+    trailing_spaces: usize,
+    quote_state: QuoteState,
+    last_rule_no_space_after: bool,
+    last_rule_is_opening: bool,
+}
+
+impl ScanState {
+    /// Record the state at the start of a step
+    ///
+    /// Cheap regardless of output length: steps only pop trailing spaces
+    /// before appending, so the output is restored by truncating.
+    fn snapshot(&self) -> StepSnapshot {
+        let stable_len = self.result.trim_end_matches(' ').len();
+        StepSnapshot {
+            stable_len,
+            trailing_spaces: self.result.len() - stable_len,
+            quote_state: self.quote_state,
+            last_rule_no_space_after: self.last_rule_no_space_after,
+            last_rule_is_opening: self.last_rule_is_opening,
+        }
+    }
+
+    /// Undo everything since `snapshot` was taken
+    fn restore(&mut self, snapshot: StepSnapshot) {
+        self.result.truncate(snapshot.stable_len);
+        self.result
+            .extend(std::iter::repeat_n(' ', snapshot.trailing_spaces));
+        self.quote_state = snapshot.quote_state;
+        self.last_rule_no_space_after = snapshot.last_rule_no_space_after;
+        self.last_rule_is_opening = snapshot.last_rule_is_opening;
+    }
+
+    /// Close the step that started at `step` (first word, stable output
+    /// length) now that the loop reached word `i`, and start the next one
+    ///
+    /// Steps only ever pop trailing spaces before appending, so output
+    /// before the trimmed length at the start of a step is never touched.
+    fn record_step(&mut self, step: &mut Option<(usize, usize)>, i: usize) {
+        let Some(spans) = self.step_spans.as_mut() else {
+            return;
+        };
+
+        if let Some((first, stable)) = step.take() {
+            let end = self.result.trim_end_matches(' ').len().max(stable);
+            let start = end - self.result[stable..end].trim_start_matches(' ').len();
+            spans.push((first..i, start..end));
+        }
+        *step = Some((i, self.result.trim_end_matches(' ').len()));
+    }
 
-    // Pre-lowercase all words once to avoid repeated allocations
-    let words_lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    /// Apply a matched rule for the `source` words
+    ///
+    /// With `literal_inside_quotes`, punctuation rules inside an open quote
+    /// emit their source words unchanged instead.
+    fn apply_rule(&mut self, rule: &TransformRule, source: &[&str], options: &TransformOptions) {
+        let is_punctuation = matches!(
+            &*rule.replacement,
+            "," | "." | "?" | "!" | ":" | ";" | "..."
+        );
+        if options.literal_inside_quotes && is_punctuation && !self.quote_state.is_balanced() {
+            for word in source {
+                self.push_word(word);
+            }
+            return;
+        }
+
+        apply_rule_with_state(&mut self.result, rule, &mut self.quote_state, options);
+        // Operators like "hyphen" only attach the next word when collapsing;
+        // compact rules ("underscore") always do
+        self.last_rule_no_space_after = rule.no_space_after
+            && (rule.attach_to_prev || options.spacing.collapse_operator_spaces);
+        self.last_rule_is_opening = rule.is_opening;
+    }
+
+    /// Append a pass-through word with regular word spacing
+    fn push_word(&mut self, word: &str) {
+        if !self.result.is_empty() {
+            let last_char = self.result.chars().last();
+            // Add space unless last rule had no_space_after or is_opening, or last char is quote/whitespace
+            let needs_space = if self.last_rule_no_space_after || self.last_rule_is_opening {
+                false
+            } else {
+                match last_char {
+                    Some('"') | Some('\'') | Some('`') => false,
+                    Some(c) if c.is_whitespace() => false,
+                    _ => true,
+                }
+            };
+
+            if needs_space {
+                self.result.push(' ');
+            }
+        }
+        self.result.push_str(word);
+        self.last_rule_no_space_after = false;
+        self.last_rule_is_opening = false;
+    }
+}
+
+/// Rule lookup keys for `words`: lowercased, or verbatim with
+/// `case_sensitive_commands` so only lowercase tokens match rules
+pub(crate) fn lookup_words(words: &[&str], options: &TransformOptions) -> Vec<String> {
+    if options.case_sensitive_commands {
+        words.iter().map(|w| w.to_string()).collect()
+    } else {
+        words.iter().map(|w| w.to_lowercase()).collect()
+    }
+}
+
+/// Maximum number of words a single loop step may look at past its start
+/// ("the word" + a 3-word pattern)
+pub(crate) const MAX_STEP_WORDS: usize = 5;
+
+/// Run the transform loop over `words`, starting steps only while `i < limit`.
+///
+/// Returns the index of the first unconsumed word. Steps starting before
+/// `limit` may consume words past it. If `limit < words.len()` more words are
+/// expected, so a step that consumes the last word is undone and left for
+/// the next call: with more words, a number, decimal or NATO run could have
+/// continued. Likewise a step waits for more words if an open literal block
+/// has no "end literal" yet, or a phone number's digits or a number that
+/// may take a unit reach the last word.
+pub(crate) fn scan_words(
+    words: &[&str],
+    words_lower: &[String],
+    limit: usize,
+    state: &mut ScanState,
+    options: &TransformOptions,
+    rules: &RuntimeRules,
+    analysis: &mut TransformAnalysis,
+) -> usize {
+    let mut i = 0;
 
     // Reusable buffer for pattern matching keys
     let mut key_buf = String::with_capacity(50);
+    let mut step = None;
+    let more_input = limit < words.len();
+    let mut snapshot = None;
+    let mut unclosed_from = usize::MAX;
+
+    while i < limit.min(words.len()) {
+        state.record_step(&mut step, i);
+        if more_input {
+            snapshot = Some((i, state.snapshot()));
+        }
 
-    while i < words.len() {
         // ========================================
         // LAYER 1: Escape/Literal Detection (v2)
         // Process FIRST to override all other layers
         // ========================================
+        // Block escape: "literal ... end literal" or "begin literal ... end
+        // literal" passes everything in between through verbatim. While more
+        // words are expected an unclosed block may still be closed, so the
+        // step waits for them; otherwise "literal" is a single-word escape.
+        match literal_block(words_lower, i, limit, &mut unclosed_from) {
+            Some(LiteralBlock::Closed(block)) => {
+                for word in &words[block.clone()] {
+                    if !state.result.is_empty() && !state.result.ends_with(' ') {
+                        state.result.push(' ');
+                    }
+                    state.result.push_str(word);
+                }
+
+                state.last_rule_no_space_after = false;
+                state.last_rule_is_opening = false;
+                i = block.end + 2;
+                continue;
+            }
+            Some(LiteralBlock::Unclosed) if more_input => return i,
+            _ => {}
+        }
+
         // Patterns: "literal X", "the word X", "literally X", "say X"
         let escape_trigger = match words_lower[i].as_str() {
             "literal" | "literally" | "say" => Some(1), // single-word trigger
@@ -193,7 +801,7 @@ pub fn transform(text: &str) -> String {
                     key_buf.push_str(&words_lower[escaped_start]);
                     key_buf.push(' ');
                     key_buf.push_str(&words_lower[escaped_start + 1]);
-                    if STATIC_MAPPINGS.contains_key(key_buf.as_str()) {
+                    if rules.find(&key_buf).is_some() {
                         escaped_words = 2;
                     }
                 }
@@ -204,188 +812,538 @@ pub fn transform(text: &str) -> String {
                     key_buf.push_str(&words_lower[escaped_start + 1]);
                     key_buf.push(' ');
                     key_buf.push_str(&words_lower[escaped_start + 2]);
-                    if STATIC_MAPPINGS.contains_key(key_buf.as_str()) {
+                    if rules.find(&key_buf).is_some() {
                         escaped_words = 3;
                     }
                 }
 
                 // Output the escaped word(s) literally (preserve original casing)
                 for j in 0..escaped_words {
-                    if !result.is_empty() && !result.ends_with(' ') {
-                        result.push(' ');
+                    if !state.result.is_empty() && !state.result.ends_with(' ') {
+                        state.result.push(' ');
                     }
-                    result.push_str(words[escaped_start + j]);
+                    state.result.push_str(words[escaped_start + j]);
                 }
 
-                last_rule_no_space_after = false;
-                last_rule_is_opening = false;
+                state.last_rule_no_space_after = false;
+                state.last_rule_is_opening = false;
                 i = escaped_start + escaped_words;
                 continue;
             }
         }
 
         // ========================================
-        // LAYER 2 & 3: Pattern Matching
+        // Snippet Commands (opt-in): "arrow function" → "() => {  }"
         // ========================================
-        // Check for multi-word patterns first (longest to shortest: 4, 3, 2 words)
-        let mut matched = false;
+        if options.snippets {
+            let mut snippet = None;
+            for len in [3, 2] {
+                if i + len <= words.len() {
+                    key_buf.clear();
+                    for (n, word) in words_lower[i..i + len].iter().enumerate() {
+                        if n > 0 {
+                            key_buf.push(' ');
+                        }
+                        key_buf.push_str(word);
+                    }
+                    if let Some(&template) = SNIPPETS.get(key_buf.as_str()) {
+                        snippet = Some((template, len));
+                        break;
+                    }
+                }
+            }
 
-        // Try 4-word pattern
-        if i + 3 < words.len() {
-            key_buf.clear();
-            key_buf.push_str(&words_lower[i]);
-            key_buf.push(' ');
-            key_buf.push_str(&words_lower[i + 1]);
-            key_buf.push(' ');
-            key_buf.push_str(&words_lower[i + 2]);
-            key_buf.push(' ');
-            key_buf.push_str(&words_lower[i + 3]);
+            if let Some((template, len)) = snippet {
+                let cursor = options.snippet_cursor.as_deref().unwrap_or("");
+                state.push_word(&template.replace(SNIPPET_CURSOR, cursor));
+                i += len;
+                continue;
+            }
+        }
 
-            if let Some(rule) = STATIC_MAPPINGS.get(key_buf.as_str()) {
-                apply_rule_with_state(&mut result, rule, &mut quote_state);
-                last_rule_no_space_after = rule.no_space_after;
-                last_rule_is_opening = rule.is_opening;
-                i += 4;
-                matched = true;
+        // ========================================
+        // Markdown List Items (opt-in), only at the start of a line:
+        // "bullet buy milk" → "- buy milk", "number one first item" → "1. first item"
+        // ========================================
+        if options.markdown_lists && at_line_start(&state.result) {
+            if let Some((marker, len)) = list_marker(words_lower, i) {
+                state.result.push_str(&marker);
+                state.result.push(' ');
+                state.last_rule_no_space_after = false;
+                state.last_rule_is_opening = false;
+                i += len;
+                continue;
             }
         }
 
-        // Try 3-word pattern
-        if !matched && i + 2 < words.len() {
+        // ========================================
+        // Markup Tags: "open tag div close tag" → "<div>",
+        // "close open tag div" → "</div>", "self closing tag br" → "<br/>"
+        // ========================================
+        if let Some((tag, len)) = spoken_tag(words, words_lower, i) {
+            state.push_word(&tag);
+            i += len;
+            continue;
+        }
+
+        // ========================================
+        // LAYER 2 & 3: Pattern Matching
+        // ========================================
+        // Check for multi-word patterns first, longest to shortest
+        // (`rules.max_pattern_words` down to 2 words)
+        let pattern = (2..=rules.max_pattern_words).rev().find_map(|len| {
+            if i + len > words.len() {
+                return None;
+            }
             key_buf.clear();
-            key_buf.push_str(&words_lower[i]);
-            key_buf.push(' ');
-            key_buf.push_str(&words_lower[i + 1]);
-            key_buf.push(' ');
-            key_buf.push_str(&words_lower[i + 2]);
-
-            if let Some(rule) = STATIC_MAPPINGS.get(key_buf.as_str()) {
-                apply_rule_with_state(&mut result, rule, &mut quote_state);
-                last_rule_no_space_after = rule.no_space_after;
-                last_rule_is_opening = rule.is_opening;
+            for (n, word) in words_lower[i..i + len].iter().enumerate() {
+                if n > 0 {
+                    key_buf.push(' ');
+                }
+                key_buf.push_str(word);
+            }
+            rules.find(&key_buf).map(|rule| (rule, len))
+        });
+
+        if let Some((rule, len)) = pattern {
+            state.apply_rule(rule, &words[i..i + len], options);
+            i += len;
+            continue;
+        }
+
+        // Check for "number" or "digit" keyword trigger: "number forty two" → "42"
+        // v2: Uses NUMBER_WORDS lookup (not STATIC_MAPPINGS) since number words pass through standalone
+        let is_number_trigger = words_lower[i] == "number" || words_lower[i] == "digit";
+        if is_number_trigger && i + 1 < words.len() {
+            let (number_str, words_consumed) =
+                parse_number(words_lower, i + 1, options.spoken_decimals);
+            if words_consumed > 0 {
+                if !state.result.is_empty() {
+                    let last_char = state.result.chars().last();
+                    let needs_space = match last_char {
+                        Some('(') | Some('[') | Some('{') | Some('"') | Some('\'') | Some('`') => {
+                            false
+                        }
+                        Some(c) if c.is_whitespace() => false,
+                        _ => true,
+                    };
+                    if needs_space {
+                        state.result.push(' ');
+                    }
+                }
+                state.result.push_str(&number_str);
+                state.last_rule_no_space_after = false;
+                state.last_rule_is_opening = false;
+                i += 1 + words_consumed; // "number" + number words
+                continue;
+            }
+        }
+
+        // Ordinals after "the": "the third" → "the 3rd". Elsewhere ordinal
+        // words pass through like number words ("wait one second")
+        if words_lower[i] == "the" {
+            let (ordinal, words_consumed) = parse_ordinal_words(words_lower, i + 1);
+            if words_consumed > 0 {
+                state.push_word(words[i]);
+                state.push_word(&ordinal);
+                i += 1 + words_consumed;
+                continue;
+            }
+        }
+
+        // Units and currencies (opt-in): "five dollars" → "$5",
+        // "three point five kilograms" → "3.5 kg"
+        if let Some(table) = &options.units {
+            let (number, words_consumed) = match parse_decimal_words(words_lower, i) {
+                (_, 0) => parse_cardinal_words(words_lower, i),
+                decimal => decimal,
+            };
+            // The unit word may not have arrived yet
+            if more_input && words_consumed > 0 && i + words_consumed == words.len() {
+                return i;
+            }
+            let formatted = words_lower
+                .get(i + words_consumed)
+                .filter(|_| words_consumed > 0)
+                .and_then(|unit| table.format(&number, unit));
+            if let Some(formatted) = formatted {
+                state.push_word(&formatted);
+                i += words_consumed + 1;
+                continue;
+            }
+        }
+
+        // Spoken dates (opt-in): "march fifteenth twenty twenty four" →
+        // "March 15, 2024"
+        if let Some(format) = options.date_format {
+            let (date, words_consumed) = parse_date_words(words_lower, i, format);
+            if words_consumed > 0 {
+                state.push_word(&date);
+                i += words_consumed;
+                continue;
+            }
+        }
+
+        // NATO phonetic alphabet (opt-in): "alpha bravo niner" → "AB9".
+        // Single words are names in prose ("I met mike"), so only runs of
+        // two or more are decoded; "niner" is never a name
+        if options.nato_phonetic {
+            let code: String = words[i..]
+                .iter()
+                .map_while(|word| NATO_PHONETIC.get(word.to_lowercase().as_str()))
+                .collect();
+            if code.chars().count() >= 2 || words[i].eq_ignore_ascii_case("niner") {
+                state.push_word(&code);
+                i += code.chars().count();
+                continue;
+            }
+        }
+
+        // Letter pairs joined by "and" (opt-in): "R and D" → "R&D"
+        if options.ampersand_initialisms {
+            if let Some(initialism) = ampersand_initialism(words, words_lower, i) {
+                state.push_word(&initialism);
                 i += 3;
                 continue;
             }
         }
 
-        // Try 2-word pattern
-        if !matched && i + 1 < words.len() {
-            key_buf.clear();
-            key_buf.push_str(&words_lower[i]);
-            key_buf.push(' ');
-            key_buf.push_str(&words_lower[i + 1]);
-
-            if let Some(rule) = STATIC_MAPPINGS.get(key_buf.as_str()) {
-                apply_rule_with_state(&mut result, rule, &mut quote_state);
-                last_rule_no_space_after = rule.no_space_after;
-                last_rule_is_opening = rule.is_opening;
+        // Spoken decimals (opt-in): "three point one four" → "3.14"
+        if options.spoken_decimals {
+            let (decimal, words_consumed) = parse_decimal_words(words_lower, i);
+            if words_consumed > 0 {
+                state.push_word(&decimal);
+                i += words_consumed;
+                continue;
+            }
+        }
+
+        // Heading markers, only at the start of a line so prose such as
+        // "we are heading two miles north" is left alone:
+        // "heading two setup" → "## Setup"
+        if words_lower[i] == "heading" && i + 1 < words.len() && at_line_start(&state.result) {
+            if let Some(&level @ 1..=6) = NUMBER_WORDS.get(words_lower[i + 1].as_str()) {
+                if options.heading_style == HeadingStyle::Markdown {
+                    state.push_word(&"#".repeat(level as usize));
+                }
                 i += 2;
+
+                // Capitalize the first word of the heading text
+                if i < words.len() && rules.find(&words_lower[i]).is_none() {
+                    state.push_word(&capitalize_first(words[i]));
+                    i += 1;
+                }
                 continue;
             }
         }
 
-        if !matched {
-            // Check for "number" or "digit" keyword trigger: "number forty two" → "42"
-            // v2: Uses NUMBER_WORDS lookup (not STATIC_MAPPINGS) since number words pass through standalone
-            let is_number_trigger = words_lower[i] == "number" || words_lower[i] == "digit";
-            if is_number_trigger && i + 1 < words.len() {
-                let (number_str, words_consumed) = parse_number_words(&words_lower, i + 1);
-                if words_consumed > 0 {
-                    if !result.is_empty() {
-                        let last_char = result.chars().last();
-                        let needs_space = match last_char {
-                            Some('(') | Some('[') | Some('{') | Some('"') | Some('\'')
-                            | Some('`') => false,
-                            Some(c) if c.is_whitespace() => false,
-                            _ => true,
-                        };
-                        if needs_space {
-                            result.push(' ');
-                        }
-                    }
-                    result.push_str(&number_str);
-                    last_rule_no_space_after = false;
-                    last_rule_is_opening = false;
-                    i += 1 + words_consumed; // "number" + number words
+        // Phone numbers (opt-in): "call five five five one two three four" → "call 555-1234"
+        if let Some(format) = &options.phone_format {
+            if matches!(words_lower[i].as_str(), "phone" | "call") {
+                let digits: String = words_lower[i + 1..]
+                    .iter()
+                    .map_while(|word| NUMBER_WORDS.get(word.as_str()).filter(|&&d| d <= 9))
+                    .map(|d| d.to_string())
+                    .collect();
+                // More digits may follow a run reaching the last word
+                if more_input && i + 1 + digits.len() == words.len() {
+                    return i;
+                }
+                if let Some(number) = format.format(&digits) {
+                    state.push_word(words[i]);
+                    state.push_word(&number);
+                    i += 1 + digits.len();
                     continue;
                 }
             }
+        }
 
-            // ========================================
-            // Contextual Number Triggers (v2): "line X", "version X", etc.
-            // These keep the prefix word: "line forty two" → "line 42"
-            // ========================================
-            if let Some(&prefix) = CONTEXTUAL_NUMBER_TRIGGERS.get(words_lower[i].as_str()) {
-                if i + 1 < words.len() {
-                    // Try to parse number words following the trigger
-                    let (number_str, words_consumed) = parse_number_words(&words_lower, i + 1);
-                    if words_consumed > 0 {
-                        // Output: prefix + space + number
-                        if !result.is_empty() && !result.ends_with(' ') {
-                            result.push(' ');
-                        }
-                        result.push_str(prefix);
-                        result.push(' ');
-                        result.push_str(&number_str);
-                        last_rule_no_space_after = false;
-                        last_rule_is_opening = false;
-                        i += 1 + words_consumed; // trigger + number words
-                        continue;
+        // ========================================
+        // Contextual Number Triggers (v2): "line X", "version X", etc.
+        // These keep the prefix word: "line forty two" → "line 42"
+        // ========================================
+        // With fuzzy triggers enabled, a near miss ("lion forty two")
+        // emits the corrected trigger word
+        let trigger = CONTEXTUAL_NUMBER_TRIGGERS
+            .get(words_lower[i].as_str())
+            .copied()
+            .or_else(|| {
+                let before_number = words_lower
+                    .get(i + 1)
+                    .is_some_and(|next| NUMBER_WORDS.contains_key(next.as_str()));
+                before_number
+                    .then(|| rules.fuzzy_trigger(&words_lower[i]))
+                    .flatten()
+            });
+        if let Some(prefix) = trigger {
+            if i + 1 < words.len() {
+                // Try to parse number words following the trigger
+                let (number_str, words_consumed) =
+                    parse_number(words_lower, i + 1, options.spoken_decimals);
+                if words_consumed > 0 {
+                    // Output: prefix + space + number
+                    if !state.result.is_empty() && !state.result.ends_with(' ') {
+                        state.result.push(' ');
                     }
+                    state.result.push_str(prefix);
+                    state.result.push(' ');
+                    state.result.push_str(&number_str);
+                    analysis.contextual_triggers.push(TriggerMatch {
+                        trigger: prefix.to_string(),
+                        position: i,
+                        number: number_str,
+                    });
+                    state.last_rule_no_space_after = false;
+                    state.last_rule_is_opening = false;
+                    i += 1 + words_consumed; // trigger + number words
+                    continue;
                 }
             }
+        }
 
-            // v2: Compound numbers WITHOUT "number" keyword pass through unchanged
-            // Use "number forty two" or contextual trigger "line forty two" for conversion
-
-            // Single word pattern or pass-through
-            if let Some(rule) = STATIC_MAPPINGS.get(words_lower[i].as_str()) {
-                apply_rule_with_state(&mut result, rule, &mut quote_state);
-                last_rule_no_space_after = rule.no_space_after;
-                last_rule_is_opening = rule.is_opening;
-                i += 1;
-            } else {
-                // Regular word - pass through
-                if !result.is_empty() {
-                    let last_char = result.chars().last();
-                    // Add space unless last rule had no_space_after or is_opening, or last char is quote/whitespace
-                    let needs_space = if last_rule_no_space_after || last_rule_is_opening {
-                        false
-                    } else {
-                        match last_char {
-                            Some('"') | Some('\'') | Some('`') => false,
-                            Some(c) if c.is_whitespace() => false,
-                            _ => true,
-                        }
-                    };
+        // v2: Compound numbers WITHOUT "number" keyword pass through unchanged
+        // Use "number forty two" or contextual trigger "line forty two" for conversion
 
-                    if needs_space {
-                        result.push(' ');
-                    }
-                }
-                result.push_str(words[i]);
-                last_rule_no_space_after = false;
-                last_rule_is_opening = false;
-                i += 1;
+        // Compound words (opt-in): "state hyphen of" → "state-of"
+        if options.compound_hyphens
+            && words_lower[i] == "hyphen"
+            && is_compound_hyphen(&state.result, words_lower.get(i + 1).map(String::as_str))
+        {
+            if state.result.ends_with(' ') {
+                state.result.pop();
             }
+            state.result.push('-');
+            state.last_rule_no_space_after = true;
+            state.last_rule_is_opening = false;
+            i += 1;
+            continue;
+        }
+
+        // Single word pattern or pass-through
+        if let Some(rule) = rules.find(&words_lower[i]) {
+            state.apply_rule(rule, &words[i..i + 1], options);
+            i += 1;
+        } else {
+            // Regular word - pass through
+            state.push_word(words[i]);
+            i += 1;
         }
     }
 
-    result
+    if let Some((start, snapshot)) = snapshot.filter(|_| i >= words.len()) {
+        state.restore(snapshot);
+        return start;
+    }
+    state.record_step(&mut step, i);
+
+    i
 }
 
-/// Track quote state for context-aware transformation
-#[derive(Default)]
-struct QuoteState {
-    double_quote_open: bool,
-    single_quote_open: bool,
-    backtick_open: bool,
+/// Whether nothing but spaces has been emitted since the last line break
+fn at_line_start(result: &str) -> bool {
+    let trimmed = result.trim_end_matches(' ');
+    trimmed.is_empty() || trimmed.ends_with('\n')
+}
+
+/// Spoken XML/HTML tag starting at `i` and the number of words it spans
+///
+/// The tag name is the single word after the trigger, with its original
+/// casing. A trailing "close tag" is optional and consumed if present.
+fn spoken_tag(words: &[&str], words_lower: &[String], i: usize) -> Option<(String, usize)> {
+    let word = |k: usize| words_lower.get(k).map(String::as_str);
+    let (trigger_len, open, close) = match (word(i)?, word(i + 1), word(i + 2)) {
+        ("open", Some("tag"), _) => (2, "<", ">"),
+        ("close", Some("open"), Some("tag")) => (3, "</", ">"),
+        ("self", Some("closing"), Some("tag")) => (3, "<", "/>"),
+        _ => return None,
+    };
+
+    let name_idx = i + trigger_len;
+    if word(name_idx)? == "close" && word(name_idx + 1) == Some("tag") {
+        return None;
+    }
+
+    let mut len = trigger_len + 1;
+    if word(i + len) == Some("close") && word(i + len + 1) == Some("tag") {
+        len += 2;
+    }
+    Some((format!("{}{}{}", open, words[name_idx], close), len))
+}
+
+/// Spoken list marker starting at `i` and the number of words it spans
+///
+/// "bullet", "bullet point" and "dash point" give "-"; "number" followed by
+/// number words gives "N.".
+fn list_marker(words_lower: &[String], i: usize) -> Option<(String, usize)> {
+    let next = words_lower.get(i + 1).map(String::as_str);
+    match (words_lower[i].as_str(), next) {
+        ("bullet" | "dash", Some("point")) => Some(("-".to_string(), 2)),
+        ("bullet", _) => Some(("-".to_string(), 1)),
+        ("number", Some(_)) => {
+            let (number, consumed) = parse_cardinal_words(words_lower, i + 1);
+            (consumed > 0).then(|| (format!("{}.", number), 1 + consumed))
+        }
+        _ => None,
+    }
+}
+
+/// "X and Y" starting at `i` collapsed to "X&Y"
+///
+/// Both sides must be single ASCII letters of the same case, so "R and D"
+/// and "q and a" match but "I and a" does not.
+fn ampersand_initialism(words: &[&str], words_lower: &[String], i: usize) -> Option<String> {
+    let letter = |word: &str| {
+        let mut chars = word.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => Some(c),
+            _ => None,
+        }
+    };
+    if words_lower.get(i + 1)? != "and" {
+        return None;
+    }
+    let left = letter(words[i])?;
+    let right = letter(words.get(i + 2)?)?;
+    (left.is_ascii_uppercase() == right.is_ascii_uppercase()).then(|| format!("{}&{}", left, right))
+}
+
+/// Uppercase the first character of `word`
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Longest phrase, in words, probed by default in the pattern matching layer
+pub(crate) const DEFAULT_MAX_PATTERN_WORDS: usize = 4;
+
+/// Rules added to a `Transformer` at runtime
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeRules {
+    /// Aliases keyed by normalized lowercase phrase
+    pub(crate) aliases: HashMap<String, TransformRule>,
+    /// Matcher for near-miss contextual triggers (off if `None`)
+    pub(crate) fuzzy_triggers: Option<FuzzyTriggers>,
+    /// Longest multi-word phrase to probe
+    pub(crate) max_pattern_words: usize,
+}
+
+impl Default for RuntimeRules {
+    fn default() -> Self {
+        Self {
+            aliases: HashMap::new(),
+            fuzzy_triggers: None,
+            max_pattern_words: DEFAULT_MAX_PATTERN_WORDS,
+        }
+    }
+}
+
+impl RuntimeRules {
+    /// Look up a rule by its lowercase phrase, preferring runtime aliases
+    fn find(&self, key: &str) -> Option<&TransformRule> {
+        self.aliases.get(key).or_else(|| STATIC_MAPPINGS.get(key))
+    }
+
+    /// Closest contextual trigger to a misheard word ("lion" → "line")
+    fn fuzzy_trigger(&self, word: &str) -> Option<&'static str> {
+        let fuzzy = self.fuzzy_triggers.as_ref()?;
+        let (trigger, similarity) = fuzzy.matcher.closest(word, &fuzzy.triggers)?;
+        if similarity < fuzzy.threshold {
+            return None;
+        }
+
+        CONTEXTUAL_NUMBER_TRIGGERS.get(trigger).copied()
+    }
+}
+
+/// Longest word considered for fuzzy trigger matching
+const MAX_FUZZY_TRIGGER_LEN: usize = 32;
+
+/// Fuzzy matcher over the contextual trigger words, built once when fuzzy
+/// triggers are enabled and shared between clones
+#[derive(Clone)]
+pub(crate) struct FuzzyTriggers {
+    matcher: Arc<v3::FuzzyMatcher>,
+    /// Trigger words, sorted so ties resolve the same way every run
+    triggers: Arc<[String]>,
+    /// Minimum similarity for a near miss to fire
+    threshold: f64,
+}
+
+impl FuzzyTriggers {
+    pub(crate) fn new(threshold: f64) -> Self {
+        let mut triggers: Vec<String> = CONTEXTUAL_NUMBER_TRIGGERS
+            .keys()
+            .map(|trigger| trigger.to_string())
+            .collect();
+        triggers.sort_unstable();
+
+        // Accept any distance here and filter on similarity in `fuzzy_trigger`
+        let matcher = v3::FuzzyMatcher::new(triggers.len(), MAX_FUZZY_TRIGGER_LEN, 1.0);
+        Self {
+            matcher: Arc::new(matcher),
+            triggers: triggers.into(),
+            threshold,
+        }
+    }
+}
+
+impl std::fmt::Debug for FuzzyTriggers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FuzzyTriggers")
+            .field("triggers", &self.triggers)
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Track quote state for context-aware transformation
+///
+/// Spoken quote words toggle between opening and closing; this records which
+/// kinds are currently open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuoteState {
+    pub double_quote_open: bool,
+    pub single_quote_open: bool,
+    pub backtick_open: bool,
+}
+
+impl QuoteState {
+    /// True when no quote of any kind is open
+    pub fn is_balanced(&self) -> bool {
+        !(self.double_quote_open || self.single_quote_open || self.backtick_open)
+    }
+}
+
+/// Whether a spoken "hyphen" joins the previous output and `next` into a
+/// compound word
+///
+/// Both sides must be plain words; a single-letter `next` reads as a
+/// command-line flag ("hyphen m" → "-m") instead.
+fn is_compound_hyphen(result: &str, next: Option<&str>) -> bool {
+    let prev_is_word = result.chars().last().is_some_and(char::is_alphanumeric);
+    let next_is_word = next.is_some_and(|word| {
+        word.chars().count() > 1
+            && word.chars().all(char::is_alphabetic)
+            && !STATIC_MAPPINGS.contains_key(word)
+    });
+
+    prev_is_word && next_is_word
 }
 
 /// Apply transformation with quote state tracking
-fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut QuoteState) {
+fn apply_rule_with_state(
+    result: &mut String,
+    rule: &TransformRule,
+    state: &mut QuoteState,
+    options: &TransformOptions,
+) {
+    let replacement: &str = &rule.replacement;
+
     // Special handling for keyboard actions: attach without spaces
-    let is_key_action = rule.replacement.starts_with("<KEY:");
+    let is_key_action = replacement.starts_with("<KEY:");
 
     if is_key_action {
         // Keyboard actions: remove trailing space and attach directly
@@ -394,7 +1352,7 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
         if result.ends_with(' ') {
             result.pop();
         }
-        result.push_str(rule.replacement);
+        result.push_str(replacement);
         // Don't add space after - let multiple keys concatenate directly
         return;
     }
@@ -405,13 +1363,21 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
         if result.ends_with(' ') {
             result.pop();
         }
-        result.push_str(rule.replacement);
+        // Compact rules ("dot" in URLs) are not sentence punctuation
+        if options.output_format == OutputFormat::Ssml && !rule.no_space_after {
+            if let Some(strength) = OutputFormat::ssml_break_strength(replacement) {
+                result.push_str("<break strength=\"");
+                result.push_str(strength);
+                result.push_str("\"/>");
+            }
+        }
+        push_replacement(result, replacement, options);
         // no_space_after is handled by the flag, not here
     } else if rule.is_opening {
         // Quotes and brackets: distinguish between quotes (toggleable) and brackets (always opening)
-        let is_quote = matches!(rule.replacement, "\"" | "'" | "`");
+        let is_quote = matches!(replacement, "\"" | "'" | "`");
         let is_actually_closing = if is_quote {
-            match rule.replacement {
+            match replacement {
                 "\"" => {
                     let closing = state.double_quote_open;
                     state.double_quote_open = !state.double_quote_open;
@@ -439,29 +1405,33 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
             if result.ends_with(' ') {
                 result.pop();
             }
-            result.push_str(rule.replacement);
+            push_replacement(result, replacement, options);
         } else if is_quote {
             // Opening quote: add space before if needed
             if !result.is_empty() {
                 let last_char = result.chars().last();
-                let needs_space = match last_char {
-                    Some(c) if c.is_whitespace() => false,
-                    _ => true,
-                };
+                let needs_space = !matches!(last_char, Some(c) if c.is_whitespace());
 
                 if needs_space {
                     result.push(' ');
                 }
             }
-            result.push_str(rule.replacement);
+            push_replacement(result, replacement, options);
         } else {
             // Opening bracket: different behavior for brackets vs parens
             // - "[" "{" "<" attach directly ONLY if not after an operator (for "arr[i]", "generic<T>")
             // - Keep space after operators: "x = [1, 2, 3]" not "x =[1, 2, 3]"
             // - "(" has space before (for "value (x + y)")
-            let should_attach = matches!(rule.replacement, "[" | "{" | "<");
+            // Each convention can be changed through `options.spacing`.
+            let spacing = &options.spacing;
+            let is_bracket = matches!(replacement, "[" | "{" | "<");
 
-            if should_attach {
+            if is_bracket && !spacing.attach_brackets_to_identifiers {
+                if !result.is_empty() && !result.ends_with(char::is_whitespace) {
+                    result.push(' ');
+                }
+                push_replacement(result, replacement, options);
+            } else if is_bracket {
                 // Check if last character is an operator that needs space after it
                 let last_char = result.chars().last();
                 let after_operator = matches!(
@@ -482,13 +1452,15 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
 
                 if after_operator {
                     // Add space after operator before bracket: "x = [" not "x =["
-                    result.push(' ');
+                    if spacing.space_after_operator {
+                        result.push(' ');
+                    }
                 } else if result.ends_with(' ') {
                     // Remove trailing space for brackets in other contexts: "arr[i]" not "arr [i]"
                     result.pop();
                 }
 
-                result.push_str(rule.replacement);
+                push_replacement(result, replacement, options);
             } else {
                 // Opening paren: context-dependent spacing
                 // - "hello_world()" - no space (function call/definition)
@@ -498,7 +1470,7 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
                     let needs_space = match last_char {
                         Some(c) if c.is_whitespace() => false,
                         // No space after identifier characters (for function calls)
-                        Some(c) if c.is_alphanumeric() || c == '_' => false,
+                        Some(c) if c.is_alphanumeric() || c == '_' => spacing.space_before_paren,
                         // Space after other characters (operators, punctuation)
                         _ => true,
                     };
@@ -507,7 +1479,10 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
                         result.push(' ');
                     }
                 }
-                result.push_str(rule.replacement);
+                push_replacement(result, replacement, options);
+                if spacing.space_after_opening_paren {
+                    result.push(' ');
+                }
             }
         }
     } else {
@@ -527,7 +1502,22 @@ fn apply_rule_with_state(result: &mut String, rule: &TransformRule, state: &mut
                 result.push(' ');
             }
         }
-        result.push_str(rule.replacement);
+        push_replacement(result, replacement, options);
+    }
+}
+
+/// Append a rule's replacement, escaping Markdown-special characters if enabled
+fn push_replacement(result: &mut String, replacement: &str, options: &TransformOptions) {
+    if !options.markdown_escape {
+        result.push_str(replacement);
+        return;
+    }
+
+    for c in replacement.chars() {
+        if options::MARKDOWN_SPECIAL_CHARS.contains(&c) {
+            result.push('\\');
+        }
+        result.push(c);
     }
 }
 
@@ -579,6 +1569,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_literal_block() {
+        assert_eq!(
+            transform("literal open paren comma close paren end literal"),
+            "open paren comma close paren"
+        );
+        assert_eq!(
+            transform("x begin literal Open Paren period end literal period"),
+            "x Open Paren period."
+        );
+
+        // Inner "literal" is not nested; the first "end literal" closes
+        assert_eq!(
+            transform("literal a literal comma end literal comma"),
+            "a literal comma,"
+        );
+
+        // Unclosed blocks keep the single-word behavior
+        assert_eq!(transform("literal comma then comma"), "comma then,");
+        assert_eq!(transform("begin literal comma"), "begin comma");
+    }
+
     #[test]
     fn test_escape_preserves_casing() {
         assert_eq!(transform("literal Comma"), "Comma");
@@ -616,6 +1628,29 @@ mod tests {
         assert_eq!(transform("option three"), "option 3");
     }
 
+    #[test]
+    fn test_analyze_reports_contextual_triggers() {
+        let analysis = analyze("line forty two and version three");
+
+        assert_eq!(analysis.output, "line 42 and version 3");
+        assert_eq!(analysis.triggers_used(), vec!["line", "version"]);
+        assert_eq!(
+            analysis.contextual_triggers,
+            vec![
+                TriggerMatch {
+                    trigger: "line".to_string(),
+                    position: 0,
+                    number: "42".to_string(),
+                },
+                TriggerMatch {
+                    trigger: "version".to_string(),
+                    position: 4,
+                    number: "3".to_string(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_contextual_error_codes() {
         assert_eq!(transform("error four oh four"), "error 404");
@@ -686,6 +1721,573 @@ mod tests {
         assert_eq!(transform(input), expected);
     }
 
+    // ========================================
+    // Transform Options Tests
+    // ========================================
+    #[test]
+    fn test_markdown_escape() {
+        let markdown = TransformOptions::new().with_markdown_escape(true);
+
+        assert_eq!(transform_with_options("asterisk", &markdown), "\\*");
+        assert_eq!(transform("asterisk"), "*");
+
+        assert_eq!(
+            transform_with_options("snake underscore case", &markdown),
+            "snake\\_case"
+        );
+        assert_eq!(
+            transform_with_options("backtick code backtick", &markdown),
+            "\\`code\\`"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escape_skips_literals_and_plain_punctuation() {
+        let markdown = TransformOptions::new().with_markdown_escape(true);
+
+        // Escaped literals are what the user explicitly wanted
+        assert_eq!(
+            transform_with_options("literal asterisk", &markdown),
+            "asterisk"
+        );
+        assert_eq!(transform_with_options("a_b", &markdown), "a_b");
+        // Punctuation that doesn't trigger inline formatting is untouched
+        assert_eq!(
+            transform_with_options("Hello comma world period", &markdown),
+            "Hello, world."
+        );
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        // An empty snippet cursor leaves a double space
+        let snippets = TransformOptions::new().with_snippets(true);
+        let input = "const f equals sign arrow function";
+        assert!(transform_with_options(input, &snippets).contains("  "));
+
+        let options = snippets.with_collapse_whitespace(true);
+        assert_eq!(
+            transform_with_options(input, &options),
+            "const f = () => { }"
+        );
+
+        // Key tokens are left alone
+        let mut transformer = Transformer::new().with_options(options.clone());
+        transformer.add_alias("select all", "<KEY:Ctrl  a>", false, false);
+        assert_eq!(
+            transformer.transform("arrow function select all"),
+            "() => { }<KEY:Ctrl  a>"
+        );
+
+        assert_eq!(
+            transform_with_options("hello backspace backspace world", &options),
+            transform("hello backspace backspace world")
+        );
+    }
+
+    #[test]
+    fn test_literal_inside_quotes() {
+        let input = "quote hello comma world quote period";
+        assert_eq!(transform(input), "\"hello, world\".");
+
+        let options = TransformOptions::new().with_literal_inside_quotes(true);
+        assert_eq!(
+            transform_with_options(input, &options),
+            "\"hello comma world\"."
+        );
+
+        // Multi-word patterns and other quote kinds stay literal too
+        assert_eq!(
+            transform_with_options("backtick a question mark b backtick", &options),
+            "`a question mark b`"
+        );
+
+        // Only punctuation words; brackets and operators still apply
+        let code = "quote f open paren x close paren equals sign y quote";
+        assert_eq!(transform_with_options(code, &options), transform(code));
+        assert_eq!(transform_with_options(code, &options), "\"f(x) = y\"");
+    }
+
+    #[test]
+    fn test_snippets() {
+        let options = TransformOptions::new().with_snippets(true);
+        assert_eq!(
+            transform_with_options("const f equals sign arrow function", &options),
+            "const f = () => {  }"
+        );
+        assert_eq!(
+            transform_with_options("console log", &options),
+            "console.log()"
+        );
+
+        let marked = options.clone().with_snippet_cursor("$0");
+        assert_eq!(
+            transform_with_options("try catch", &marked),
+            "try { $0 } catch (e) {}"
+        );
+
+        // Symbol rules still apply around snippets
+        assert_eq!(
+            transform_with_options("for loop semicolon", &options),
+            "for () {};"
+        );
+    }
+
+    #[test]
+    fn test_snippets_inert_when_disabled() {
+        assert_eq!(transform("arrow function"), "arrow function");
+        assert_eq!(transform("for loop"), "for loop");
+
+        for phrase in SNIPPETS.keys() {
+            assert!(
+                !STATIC_MAPPINGS.contains_key(phrase),
+                "snippet {:?} shadows a static rule",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn test_ssml_output() {
+        let options = TransformOptions::new().with_output_format(OutputFormat::Ssml);
+        assert_eq!(
+            transform_with_options("Hello comma world period", &options),
+            "Hello<break strength=\"medium\"/>, world<break strength=\"strong\"/>."
+        );
+        assert_eq!(
+            transform_with_options("really question mark", &options),
+            "really<break strength=\"strong\"/>?"
+        );
+
+        // Non-punctuation symbols are unaffected
+        assert_eq!(transform_with_options("a plus sign b", &options), "a + b");
+    }
+
+    #[test]
+    fn test_plain_output_unchanged() {
+        let options = TransformOptions::new().with_output_format(OutputFormat::Plain);
+        for input in [
+            "Hello comma world period",
+            "really question mark",
+            "x colon y",
+        ] {
+            assert_eq!(transform_with_options(input, &options), transform(input));
+        }
+        assert_eq!(transform("Hello comma world period"), "Hello, world.");
+    }
+
+    #[test]
+    fn test_compound_hyphens() {
+        let options = TransformOptions::new().with_compound_hyphens(true);
+        assert_eq!(
+            transform_with_options("state hyphen of hyphen the hyphen art", &options),
+            "state-of-the-art"
+        );
+        assert_eq!(
+            transform_with_options("a well hyphen known bug", &options),
+            "a well-known bug"
+        );
+
+        // Single-letter flags keep their spacing with or without the option
+        for input in [
+            "git commit hyphen m",
+            "git commit hyphen m quote fix bug quote",
+        ] {
+            assert_eq!(transform_with_options(input, &options), transform(input));
+        }
+        assert_eq!(
+            transform_with_options("git commit hyphen m", &options),
+            "git commit -m"
+        );
+    }
+
+    #[test]
+    fn test_markdown_list_items() {
+        let lists = TransformOptions::new().with_markdown_lists(true);
+
+        assert_eq!(
+            transform_with_options("bullet buy milk", &lists),
+            "- buy milk"
+        );
+        assert_eq!(
+            transform_with_options("dash point call mom comma today", &lists),
+            "- call mom, today"
+        );
+        assert_eq!(
+            transform_with_options("number one first item", &lists),
+            "1. first item"
+        );
+        assert_eq!(
+            transform_with_options(
+                "number one first item new line number two second item",
+                &lists
+            ),
+            "1. first item\n2. second item"
+        );
+
+        // Mid-line "number" keeps its usual meaning
+        assert_eq!(transform_with_options("pick number two", &lists), "pick 2");
+        assert_eq!(
+            transform_with_options("the bullet hit", &lists),
+            "the bullet hit"
+        );
+
+        // Off by default
+        assert_eq!(transform("bullet buy milk"), "bullet buy milk");
+        assert_eq!(transform("number one first item"), "1 first item");
+    }
+
+    #[test]
+    fn test_phone_numbers() {
+        let phones = TransformOptions::new().with_phone_format(PhoneFormat::new());
+
+        assert_eq!(
+            transform_with_options("call five five five one two three four", &phones),
+            "call 555-1234"
+        );
+        assert_eq!(
+            transform_with_options(
+                "my phone two oh six five five five one two one two period",
+                &phones
+            ),
+            "my phone 206-555-1212."
+        );
+
+        let dotted = TransformOptions::new().with_phone_format(
+            PhoneFormat::new()
+                .with_groupings(vec![vec![3, 3, 4]])
+                .with_separator("."),
+        );
+        assert_eq!(
+            transform_with_options("call eight oh oh five five five oh one nine nine", &dotted),
+            "call 800.555.0199"
+        );
+
+        // Digit runs matching no grouping stay as words
+        assert_eq!(
+            transform_with_options("call two friends", &phones),
+            "call two friends"
+        );
+        assert_eq!(
+            transform("call five five five one two three four"),
+            "call five five five one two three four"
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive_commands() {
+        assert_eq!(transform("Open Paren x close paren"), "(x)");
+
+        let strict = TransformOptions::new().with_case_sensitive_commands(true);
+        assert_eq!(
+            transform_with_options("Open Paren x close paren", &strict),
+            "Open Paren x)"
+        );
+        assert_eq!(
+            transform_with_options("open paren x close paren", &strict),
+            "(x)"
+        );
+        assert_eq!(
+            transform_with_options("Hello Comma world comma", &strict),
+            "Hello Comma world,"
+        );
+    }
+
+    #[test]
+    fn test_transform_with_spans() {
+        let text = "Hello question mark";
+        let (output, spans) = transform_with_spans(text);
+        assert_eq!(output, transform(text));
+        assert_eq!(spans.len(), 2);
+        assert_eq!(&output[spans[1].output_range.clone()], "?");
+        assert_eq!(&text[spans[1].input_range.clone()], "question mark");
+
+        let text = "go to  line forty two then say comma and number nine period";
+        let (output, spans) = transform_with_spans(text);
+        assert_eq!(output, transform(text));
+        let mapped: Vec<(&str, &str)> = spans
+            .iter()
+            .map(|span| {
+                (
+                    &text[span.input_range.clone()],
+                    &output[span.output_range.clone()],
+                )
+            })
+            .collect();
+        assert_eq!(
+            mapped,
+            vec![
+                ("go", "go"),
+                ("to", "to"),
+                ("line forty two", "line 42"),
+                ("then", "then"),
+                ("say comma", "comma"),
+                ("and", "and"),
+                ("number nine", "9"),
+                ("period", "."),
+            ]
+        );
+
+        // Brackets attach to their neighbors; key actions collapse spaces
+        let text = "call open paren x close paren backspace";
+        let (output, spans) = transform_with_spans(text);
+        assert_eq!(output, transform(text));
+        for span in &spans {
+            assert!(span.output_range.end <= output.len());
+            assert!(!output[span.output_range.clone()].starts_with(' '));
+        }
+        assert_eq!(&output[spans[1].output_range.clone()], "(");
+        assert_eq!(&output[spans[3].output_range.clone()], ")");
+
+        assert_eq!(transform_with_spans("   "), (String::new(), Vec::new()));
+    }
+
+    #[test]
+    fn test_markup_tags() {
+        assert_eq!(transform("open tag div close tag"), "<div>");
+        assert_eq!(transform("close open tag div"), "</div>");
+        assert_eq!(transform("self closing tag br"), "<br/>");
+        assert_eq!(
+            transform("open tag p close tag hello close open tag p close tag"),
+            "<p> hello </p>"
+        );
+
+        // Without a tag name the words pass through
+        assert_eq!(transform("open tag"), "open tag");
+        assert_eq!(transform("open tag close tag"), "open tag close tag");
+    }
+
+    #[test]
+    fn test_heading_markers() {
+        assert_eq!(transform("heading one introduction"), "# Introduction");
+        assert_eq!(
+            transform("heading two getting started"),
+            "## Getting started"
+        );
+        assert_eq!(
+            transform("intro new line heading three API notes"),
+            "intro\n### API notes"
+        );
+
+        let plain = TransformOptions::new().with_heading_style(HeadingStyle::Plain);
+        assert_eq!(transform_with_options("heading two setup", &plain), "Setup");
+
+        // No level number: plain words
+        assert_eq!(transform("heading introduction"), "heading introduction");
+        assert_eq!(transform("the heading"), "the heading");
+
+        // Mid-sentence "heading" followed by a number is prose
+        assert_eq!(
+            transform("we are heading two miles north"),
+            "we are heading two miles north"
+        );
+        assert_eq!(
+            transform("they keep heading one way or another"),
+            "they keep heading one way or another"
+        );
+    }
+
+    #[test]
+    fn test_spacing_policy_paren() {
+        assert_eq!(transform("func open paren x close paren"), "func(x)");
+
+        let spaced = TransformOptions::new()
+            .with_spacing_policy(SpacingPolicy::new().with_space_before_paren(true));
+        assert_eq!(
+            transform_with_options("func open paren x close paren", &spaced),
+            "func (x)"
+        );
+
+        let default = TransformOptions::new().with_spacing_policy(SpacingPolicy::default());
+        assert_eq!(
+            transform_with_options("func open paren x close paren", &default),
+            "func(x)"
+        );
+    }
+
+    #[test]
+    fn test_spacing_policy_brackets() {
+        let detached = TransformOptions::new()
+            .with_spacing_policy(SpacingPolicy::new().with_attach_brackets_to_identifiers(false));
+        assert_eq!(
+            transform_with_options("arr open bracket i close bracket", &detached),
+            "arr [i]"
+        );
+
+        let tight = TransformOptions::new()
+            .with_spacing_policy(SpacingPolicy::new().with_space_after_operator(false));
+        assert_eq!(
+            transform_with_options("x equals sign open bracket one close bracket", &tight),
+            "x =[one]"
+        );
+    }
+
+    #[test]
+    fn test_transform_with_config() {
+        let inputs = [
+            "git commit hyphen m quote fix quote",
+            "func open paren x comma y close paren",
+            "snake underscore case",
+        ];
+        for input in inputs {
+            assert_eq!(
+                transform_with_config(input, &SpacingConfig::default()),
+                transform(input)
+            );
+        }
+
+        let verbose = SpacingConfig::new()
+            .with_collapse_operator_spaces(false)
+            .with_space_after_opening_paren(true);
+        assert_eq!(
+            transform_with_config("git commit hyphen m", &verbose),
+            "git commit - m"
+        );
+        assert_eq!(
+            transform_with_config("snake underscore case", &verbose),
+            "snake_case"
+        );
+        assert_eq!(
+            transform_with_config("value open paren x close paren", &verbose),
+            "value( x)"
+        );
+        assert_eq!(
+            transform_with_config("f open paren close paren", &verbose),
+            "f()"
+        );
+    }
+
+    #[test]
+    fn test_is_rule_prefix() {
+        assert!(is_rule_prefix(&["question"]));
+        assert!(is_rule_prefix(&["Open"]));
+        assert!(is_rule_prefix(&["strict", "not"]));
+        assert!(!is_rule_prefix(&["banana"]));
+
+        // Complete phrases are not prefixes unless a longer rule extends them
+        assert!(!is_rule_prefix(&["question", "mark"]));
+        assert!(!is_rule_prefix(&["period"]));
+        assert!(!is_rule_prefix(&["mark", "question"]));
+    }
+
+    #[test]
+    fn test_export_rules_json() {
+        let json = export_rules_json();
+        let rules: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let table = rules.as_object().unwrap();
+        assert_eq!(table.len(), STATIC_MAPPINGS.len());
+
+        assert_eq!(
+            rules["period"],
+            serde_json::json!({
+                "replacement": ".",
+                "attach_to_prev": true,
+                "is_opening": false,
+                "no_space_after": false,
+            })
+        );
+        assert_eq!(rules["open paren"]["is_opening"], true);
+        assert_eq!(rules["hyphen"]["no_space_after"], true);
+
+        // Sorted and stable across calls
+        assert!(json.find("\"comma\"").unwrap() < json.find("\"period\"").unwrap());
+        assert!(json.find("\"open paren\"").unwrap() < json.find("\"period\"").unwrap());
+        assert_eq!(json, export_rules_json());
+    }
+
+    #[test]
+    fn test_nato_phonetic() {
+        let options = TransformOptions::new().with_nato_phonetic(true);
+        let nato = |text: &str| transform_with_options(text, &options);
+
+        assert_eq!(nato("alpha bravo charlie"), "ABC");
+        assert_eq!(nato("niner niner"), "99");
+        assert_eq!(nato("Alpha BRAVO niner x-ray"), "AB9X");
+        assert_eq!(
+            nato("code is alpha bravo charlie please"),
+            "code is ABC please"
+        );
+        assert_eq!(nato("tango zulu period"), "TZ.");
+
+        // Case-insensitive even when commands are case sensitive
+        let strict = options.clone().with_case_sensitive_commands(true);
+        assert_eq!(transform_with_options("Delta Echo", &strict), "DE");
+
+        // Single phonetic words in prose are names, not letters
+        assert_eq!(nato("I met mike at the hotel"), "I met mike at the hotel");
+        assert_eq!(
+            nato("victor and oscar said hello to juliet"),
+            "victor and oscar said hello to juliet"
+        );
+        assert_eq!(nato("niner"), "9");
+
+        // Off by default
+        assert_eq!(transform("alpha bravo"), "alpha bravo");
+    }
+
+    #[test]
+    fn test_ampersand_initialisms() {
+        let options = TransformOptions::new().with_ampersand_initialisms(true);
+        let amp = |text: &str| transform_with_options(text, &options);
+
+        assert_eq!(amp("R and D"), "R&D");
+        assert_eq!(amp("the R and D budget"), "the R&D budget");
+        assert_eq!(amp("q and a session"), "q&a session");
+
+        // Ordinary prose is left alone
+        assert_eq!(amp("you and me"), "you and me");
+        assert_eq!(amp("I and a friend"), "I and a friend");
+        assert_eq!(amp("R and"), "R and");
+
+        // Off by default
+        assert_eq!(transform("R and D"), "R and D");
+    }
+
+    #[test]
+    fn test_spoken_decimals() {
+        let options = TransformOptions::new().with_spoken_decimals(true);
+        assert_eq!(
+            transform_with_options("three point one four", &options),
+            "3.14"
+        );
+        assert_eq!(transform_with_options("zero point five", &options), "0.5");
+        assert_eq!(
+            transform_with_options("pi is about three point one four one five nine", &options),
+            "pi is about 3.14159"
+        );
+        assert_eq!(
+            transform_with_options("version two point five", &options),
+            "version 2.5"
+        );
+
+        // Prose "point" stays a word
+        for prose in ["good point one", "three point plan", "the point is two"] {
+            assert_eq!(transform_with_options(prose, &options), prose);
+        }
+
+        // Off by default
+        assert_eq!(transform("three point one four"), "three point one four");
+    }
+
+    #[test]
+    fn test_try_transform_valid_utf8() {
+        assert_eq!(
+            try_transform("Hello comma world period".as_bytes()).unwrap(),
+            "Hello, world."
+        );
+        assert_eq!(
+            try_transform("café comma ok".as_bytes()).unwrap(),
+            "café, ok"
+        );
+    }
+
+    #[test]
+    fn test_try_transform_invalid_utf8() {
+        // Truncated multi-byte sequence: 0xC3 must be followed by a continuation byte
+        let result = try_transform(&[b'a', b' ', 0xC3]);
+        assert!(matches!(result, Err(v3::TransformError::InvalidUtf8(_))));
+    }
+
     #[test]
     fn test_performance_target() {
         use std::time::Instant;
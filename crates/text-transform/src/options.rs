@@ -0,0 +1,413 @@
+//! Options for the static `transform` pipeline
+//!
+//! `transform()` uses the defaults; `transform_with_options()` takes an
+//! explicit `TransformOptions` built with the `with_*` builder methods.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::SpacingPolicy;
+
+/// Characters that can trigger Markdown formatting when emitted by a rule
+pub(crate) const MARKDOWN_SPECIAL_CHARS: &[char] = &['\\', '`', '*', '_', '~', '[', ']', '#', '|'];
+
+/// Output format for punctuation produced by the static pipeline
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputFormat {
+    /// Raw symbols ("period" → ".")
+    #[default]
+    Plain,
+    /// Prefix spoken punctuation with SSML break hints for text-to-speech
+    /// ("period" → `<break strength="strong"/>.`)
+    Ssml,
+}
+
+impl OutputFormat {
+    /// SSML break strength for a punctuation replacement, if it gets one
+    pub(crate) fn ssml_break_strength(replacement: &str) -> Option<&'static str> {
+        match replacement {
+            "." | "?" | "!" => Some("strong"),
+            "," | ";" | ":" | "..." => Some("medium"),
+            _ => None,
+        }
+    }
+}
+
+/// How spoken heading markers ("heading two setup") are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeadingStyle {
+    /// Markdown ATX heading ("## Setup")
+    #[default]
+    Markdown,
+    /// Heading text only, capitalized ("Setup")
+    Plain,
+}
+
+/// How spoken dates ("march fifteenth twenty twenty four") are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateFormat {
+    /// "March 15, 2024", or "March 15" without a year
+    #[default]
+    MonthDayYear,
+    /// "15 March 2024", or "15 March" without a year
+    DayMonthYear,
+    /// "2024-03-15"; dates without a year fall back to "March 15"
+    Iso,
+}
+
+impl DateFormat {
+    /// Format a date; `month` is 1-based
+    pub(crate) fn format(&self, month: usize, day: i32, year: Option<&str>) -> String {
+        let name = crate::capitalize_first(crate::rules::MONTH_NAMES[month - 1]);
+        match (self, year) {
+            (DateFormat::Iso, Some(year)) => format!("{}-{:02}-{:02}", year, month, day),
+            (DateFormat::DayMonthYear, Some(year)) => format!("{} {} {}", day, name, year),
+            (DateFormat::DayMonthYear, None) => format!("{} {}", day, name),
+            (_, Some(year)) => format!("{} {}, {}", name, day, year),
+            (_, None) => format!("{} {}", name, day),
+        }
+    }
+}
+
+/// Digit grouping for spoken phone numbers
+///
+/// A run of digits is formatted with the first grouping whose sizes add up
+/// to its length; runs matching no grouping are left as words.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhoneFormat {
+    /// Group sizes, e.g. `[3, 4]` for "555-1234"
+    pub groupings: Vec<Vec<usize>>,
+    /// Text placed between groups
+    pub separator: String,
+}
+
+impl Default for PhoneFormat {
+    fn default() -> Self {
+        Self {
+            groupings: vec![vec![3, 4], vec![3, 3, 4]],
+            separator: "-".to_string(),
+        }
+    }
+}
+
+impl PhoneFormat {
+    /// Create a format with the default 3-4 and 3-3-4 groupings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Replace the groupings
+    pub fn with_groupings(mut self, groupings: Vec<Vec<usize>>) -> Self {
+        self.groupings = groupings;
+        self
+    }
+
+    /// Builder: Set the group separator
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Format `digits` with the first matching grouping
+    pub(crate) fn format(&self, digits: &str) -> Option<String> {
+        let grouping = self
+            .groupings
+            .iter()
+            .find(|groups| groups.iter().sum::<usize>() == digits.len())?;
+
+        let mut formatted = String::with_capacity(digits.len() * 2);
+        let mut rest = digits;
+        for (n, &size) in grouping.iter().enumerate() {
+            if n > 0 {
+                formatted.push_str(&self.separator);
+            }
+            let (group, tail) = rest.split_at(size);
+            formatted.push_str(group);
+            rest = tail;
+        }
+        Some(formatted)
+    }
+}
+
+/// Where a unit symbol goes relative to its number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitPlacement {
+    /// Before the number, attached ("$5")
+    Prefix,
+    /// After the number, attached ("20%")
+    Suffix,
+    /// After the number, separated by a space ("3.5 kg")
+    SpacedSuffix,
+}
+
+/// Formatting for one spoken unit or currency word
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unit {
+    /// Symbol or abbreviation emitted for the word
+    pub symbol: String,
+    /// Position of the symbol relative to the number
+    pub placement: UnitPlacement,
+}
+
+/// Spoken unit and currency words recognized after a number
+///
+/// The default covers common currencies, percentages, and metric units;
+/// locales can replace entries ("dollars" → "€") or add their own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitTable {
+    /// Units keyed by lowercase spoken word
+    pub units: BTreeMap<String, Unit>,
+}
+
+impl Default for UnitTable {
+    fn default() -> Self {
+        use UnitPlacement::*;
+
+        let entries = [
+            ("dollar", "$", Prefix),
+            ("dollars", "$", Prefix),
+            ("euro", "€", Prefix),
+            ("euros", "€", Prefix),
+            ("percent", "%", Suffix),
+            ("gram", "g", SpacedSuffix),
+            ("grams", "g", SpacedSuffix),
+            ("kilogram", "kg", SpacedSuffix),
+            ("kilograms", "kg", SpacedSuffix),
+            ("meter", "m", SpacedSuffix),
+            ("meters", "m", SpacedSuffix),
+            ("kilometer", "km", SpacedSuffix),
+            ("kilometers", "km", SpacedSuffix),
+            ("centimeter", "cm", SpacedSuffix),
+            ("centimeters", "cm", SpacedSuffix),
+            ("liter", "L", SpacedSuffix),
+            ("liters", "L", SpacedSuffix),
+        ];
+
+        Self {
+            units: entries
+                .into_iter()
+                .map(|(word, symbol, placement)| {
+                    (
+                        word.to_string(),
+                        Unit {
+                            symbol: symbol.to_string(),
+                            placement,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl UnitTable {
+    /// Create a table with the default units
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a table with no units
+    pub fn empty() -> Self {
+        Self {
+            units: BTreeMap::new(),
+        }
+    }
+
+    /// Builder: Add or replace the unit for a spoken word
+    pub fn with_unit(
+        mut self,
+        word: &str,
+        symbol: impl Into<String>,
+        placement: UnitPlacement,
+    ) -> Self {
+        self.units.insert(
+            word.to_lowercase(),
+            Unit {
+                symbol: symbol.into(),
+                placement,
+            },
+        );
+        self
+    }
+
+    /// Format `number` with the unit for `word`, if there is one
+    pub(crate) fn format(&self, number: &str, word: &str) -> Option<String> {
+        let unit = self.units.get(word)?;
+        Some(match unit.placement {
+            UnitPlacement::Prefix => format!("{}{}", unit.symbol, number),
+            UnitPlacement::Suffix => format!("{}{}", number, unit.symbol),
+            UnitPlacement::SpacedSuffix => format!("{} {}", number, unit.symbol),
+        })
+    }
+}
+
+/// Options controlling the static transformation pipeline
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransformOptions {
+    /// Backslash-escape Markdown-special characters produced by symbol rules.
+    /// Words passed through literally (e.g. "literal asterisk") are not escaped.
+    pub markdown_escape: bool,
+    /// Collapse runs of spaces in the final output to a single space.
+    /// `<KEY:...>` tokens are never modified.
+    pub collapse_whitespace: bool,
+    /// While a quote is open, emit spoken punctuation words (comma, period,
+    /// question mark, ...) literally (`quote hello comma world quote` →
+    /// `"hello comma world"`). Brackets, operators and other rules still apply.
+    pub literal_inside_quotes: bool,
+    /// Expand code snippet commands (e.g. "arrow function" → `() => {  }`)
+    pub snippets: bool,
+    /// Text inserted at the snippet cursor position (empty if `None`)
+    pub snippet_cursor: Option<String>,
+    /// Plain symbols or SSML break hints for spoken punctuation
+    pub output_format: OutputFormat,
+    /// Join "hyphen" between two words without spaces
+    /// (`state hyphen of hyphen the hyphen art` → `state-of-the-art`).
+    /// Single-letter words after "hyphen" are treated as command-line flags
+    /// and keep the usual spacing (`git commit hyphen m` → `git commit -m`).
+    pub compound_hyphens: bool,
+    /// Markdown or plain rendering of "heading one" … "heading six" at the
+    /// start of a line
+    pub heading_style: HeadingStyle,
+    /// Spacing conventions around brackets
+    pub spacing: SpacingPolicy,
+    /// Convert spoken decimals ("three point one four" → "3.14"), both on
+    /// their own and after number triggers
+    pub spoken_decimals: bool,
+    /// Turn list dictation at the start of a line into Markdown list markers
+    /// ("bullet buy milk" → "- buy milk", "number one first item" →
+    /// "1. first item")
+    pub markdown_lists: bool,
+    /// Format digit runs after "phone" or "call" as phone numbers
+    /// ("call five five five one two three four" → "call 555-1234")
+    pub phone_format: Option<PhoneFormat>,
+    /// Only match rules against all-lowercase tokens; capitalized command
+    /// words ("Open Paren") pass through as literal text
+    pub case_sensitive_commands: bool,
+    /// Format spoken numbers followed by a unit or currency word
+    /// ("five dollars" → "$5", "three point five kilograms" → "3.5 kg")
+    pub units: Option<UnitTable>,
+    /// Assemble a month name, ordinal day, and optional year into a date
+    /// ("march fifteenth twenty twenty four" → "March 15, 2024")
+    pub date_format: Option<DateFormat>,
+    /// Decode runs of two or more NATO phonetic words into letters,
+    /// case-insensitively ("alpha bravo charlie" → "ABC", "niner niner" →
+    /// "99"). A run ends at the first other word; a lone phonetic word such
+    /// as "mike" is left as a name, but a lone "niner" still gives "9".
+    pub nato_phonetic: bool,
+    /// Join two single letters around "and" with an ampersand ("R and D" →
+    /// "R&D"). Both letters must share a case, so prose such as "you and
+    /// me" or "I and a friend" is unchanged.
+    pub ampersand_initialisms: bool,
+}
+
+impl TransformOptions {
+    /// Create options with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Enable/disable Markdown escaping of emitted symbols
+    pub fn with_markdown_escape(mut self, enabled: bool) -> Self {
+        self.markdown_escape = enabled;
+        self
+    }
+
+    /// Builder: Enable/disable collapsing repeated spaces in the output
+    pub fn with_collapse_whitespace(mut self, enabled: bool) -> Self {
+        self.collapse_whitespace = enabled;
+        self
+    }
+
+    /// Builder: Enable/disable literal punctuation words inside open quotes
+    pub fn with_literal_inside_quotes(mut self, enabled: bool) -> Self {
+        self.literal_inside_quotes = enabled;
+        self
+    }
+
+    /// Builder: Enable/disable code snippet expansion
+    pub fn with_snippets(mut self, enabled: bool) -> Self {
+        self.snippets = enabled;
+        self
+    }
+
+    /// Builder: Mark the snippet cursor position with `marker` (e.g. "$0")
+    pub fn with_snippet_cursor(mut self, marker: impl Into<String>) -> Self {
+        self.snippet_cursor = Some(marker.into());
+        self
+    }
+
+    /// Builder: Set the output format
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Builder: Enable/disable joining hyphenated compound words
+    pub fn with_compound_hyphens(mut self, enabled: bool) -> Self {
+        self.compound_hyphens = enabled;
+        self
+    }
+
+    /// Builder: Set how heading markers are rendered
+    pub fn with_heading_style(mut self, style: HeadingStyle) -> Self {
+        self.heading_style = style;
+        self
+    }
+
+    /// Builder: Enable/disable spoken decimal conversion
+    pub fn with_spoken_decimals(mut self, enabled: bool) -> Self {
+        self.spoken_decimals = enabled;
+        self
+    }
+
+    /// Builder: Enable/disable Markdown list markers at line start
+    pub fn with_markdown_lists(mut self, enabled: bool) -> Self {
+        self.markdown_lists = enabled;
+        self
+    }
+
+    /// Builder: Format spoken phone numbers with `format`
+    pub fn with_phone_format(mut self, format: PhoneFormat) -> Self {
+        self.phone_format = Some(format);
+        self
+    }
+
+    /// Builder: Enable/disable case-sensitive rule matching
+    pub fn with_case_sensitive_commands(mut self, enabled: bool) -> Self {
+        self.case_sensitive_commands = enabled;
+        self
+    }
+
+    /// Builder: Format numbers followed by words from `table`
+    pub fn with_units(mut self, table: UnitTable) -> Self {
+        self.units = Some(table);
+        self
+    }
+
+    /// Builder: Assemble spoken dates in `format`
+    pub fn with_date_format(mut self, format: DateFormat) -> Self {
+        self.date_format = Some(format);
+        self
+    }
+
+    /// Builder: Enable/disable NATO phonetic alphabet decoding
+    pub fn with_nato_phonetic(mut self, enabled: bool) -> Self {
+        self.nato_phonetic = enabled;
+        self
+    }
+
+    /// Builder: Enable/disable "X and Y" → "X&Y" for single letters
+    pub fn with_ampersand_initialisms(mut self, enabled: bool) -> Self {
+        self.ampersand_initialisms = enabled;
+        self
+    }
+
+    /// Builder: Set the bracket spacing policy
+    pub fn with_spacing_policy(mut self, spacing: SpacingPolicy) -> Self {
+        self.spacing = spacing;
+        self
+    }
+}
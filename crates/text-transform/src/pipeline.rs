@@ -0,0 +1,105 @@
+//! Composable transform pipelines
+//!
+//! A `TransformPipeline` runs an ordered list of stages, feeding each stage
+//! the previous stage's output. Any `FnMut(&str) -> String` is a stage, as is
+//! a stateful [`Transformer`].
+
+use crate::Transformer;
+
+/// A single step in a [`TransformPipeline`]
+pub trait TransformStage {
+    /// Transform the output of the previous stage
+    fn apply(&mut self, text: &str) -> String;
+}
+
+impl<F> TransformStage for F
+where
+    F: FnMut(&str) -> String,
+{
+    fn apply(&mut self, text: &str) -> String {
+        self(text)
+    }
+}
+
+impl TransformStage for Transformer {
+    fn apply(&mut self, text: &str) -> String {
+        self.transform(text)
+    }
+}
+
+/// Ordered chain of transform stages applied with a single call
+#[derive(Default)]
+pub struct TransformPipeline {
+    stages: Vec<Box<dyn TransformStage>>,
+}
+
+impl TransformPipeline {
+    /// Create an empty pipeline (returns input unchanged)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Append a stage
+    pub fn with_stage(mut self, stage: impl TransformStage + 'static) -> Self {
+        self.push_stage(stage);
+        self
+    }
+
+    /// Append a stage
+    pub fn push_stage(&mut self, stage: impl TransformStage + 'static) {
+        self.stages.push(Box::new(stage));
+    }
+
+    /// Run `text` through every stage in order
+    pub fn transform(&mut self, text: &str) -> String {
+        self.stages
+            .iter_mut()
+            .fold(text.to_string(), |current, stage| stage.apply(&current))
+    }
+
+    /// Number of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Check if the pipeline has no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform;
+
+    #[test]
+    fn test_pipeline_composes_stages() {
+        let mut pipeline = TransformPipeline::new()
+            .with_stage(|text: &str| text.to_lowercase())
+            .with_stage(transform)
+            .with_stage(|text: &str| text.trim_end_matches('.').to_string());
+
+        assert_eq!(pipeline.len(), 3);
+        assert_eq!(
+            pipeline.transform("HELLO COMMA World PERIOD"),
+            "hello, world"
+        );
+    }
+
+    #[test]
+    fn test_pipeline_with_stateful_transformer() {
+        let mut pipeline = TransformPipeline::new().with_stage(Transformer::new());
+
+        // Quote state carries across calls through the Transformer stage
+        assert_eq!(pipeline.transform("quote hi"), "\"hi");
+        assert_eq!(pipeline.transform("there quote"), "there\"");
+    }
+
+    #[test]
+    fn test_empty_pipeline() {
+        let mut pipeline = TransformPipeline::new();
+        assert!(pipeline.is_empty());
+        assert_eq!(pipeline.transform("unchanged comma"), "unchanged comma");
+    }
+}
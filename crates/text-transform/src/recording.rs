@@ -0,0 +1,111 @@
+//! Recording and replay of transform sessions
+//!
+//! `RecordingTransformer` wraps a [`Transformer`] and logs every input, its
+//! output and the active options, so a bad transform reported by a user can
+//! be dumped to JSON and replayed exactly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::v3::TransformError;
+use crate::{TransformOptions, Transformer};
+
+/// A recorded sequence of transform calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransformSession {
+    /// Options active for the whole session
+    pub options: TransformOptions,
+    /// Inputs in call order
+    pub inputs: Vec<String>,
+    /// Outputs produced at record time, parallel to `inputs`
+    pub outputs: Vec<String>,
+}
+
+/// Transformer that records every call for later replay
+#[derive(Debug, Clone, Default)]
+pub struct RecordingTransformer {
+    inner: Transformer,
+    session: TransformSession,
+}
+
+impl RecordingTransformer {
+    /// Create a recording transformer with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Use explicit transform options
+    pub fn with_options(mut self, options: TransformOptions) -> Self {
+        self.inner = Transformer::new().with_options(options.clone());
+        self.session = TransformSession {
+            options,
+            ..TransformSession::default()
+        };
+        self
+    }
+
+    /// Transform `text` and record the call
+    pub fn transform(&mut self, text: &str) -> String {
+        let output = self.inner.transform(text);
+        self.session.inputs.push(text.to_string());
+        self.session.outputs.push(output.clone());
+        output
+    }
+
+    /// The session recorded so far
+    pub fn session(&self) -> &TransformSession {
+        &self.session
+    }
+
+    /// Serialize the recorded session as JSON
+    pub fn dump(&self) -> String {
+        serde_json::to_string_pretty(&self.session)
+            .expect("TransformSession serialization cannot fail")
+    }
+
+    /// Re-run a session dumped with [`RecordingTransformer::dump`]
+    ///
+    /// Inputs are replayed in order through a fresh transformer, so quote
+    /// state carries across calls exactly as it did when recorded.
+    pub fn replay(session_json: &str) -> Result<Vec<String>, TransformError> {
+        let session: TransformSession = serde_json::from_str(session_json)
+            .map_err(|e| TransformError::SerializationError(e.to_string()))?;
+
+        let mut transformer = Transformer::new().with_options(session.options);
+        Ok(session
+            .inputs
+            .iter()
+            .map(|input| transformer.transform(input))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_dump_replay() {
+        let options = TransformOptions::new().with_markdown_escape(true);
+        let mut recorder = RecordingTransformer::new().with_options(options.clone());
+
+        let outputs: Vec<String> = ["Hello comma world", "quote a asterisk b", "c quote period"]
+            .iter()
+            .map(|input| recorder.transform(input))
+            .collect();
+        assert_eq!(recorder.session().inputs.len(), 3);
+        assert_eq!(recorder.session().options, options);
+
+        let json = recorder.dump();
+        let replayed = RecordingTransformer::replay(&json).unwrap();
+        assert_eq!(replayed, outputs);
+        assert_eq!(replayed, recorder.session().outputs);
+    }
+
+    #[test]
+    fn test_replay_invalid_json() {
+        assert!(matches!(
+            RecordingTransformer::replay("not json"),
+            Err(TransformError::SerializationError(_))
+        ));
+    }
+}
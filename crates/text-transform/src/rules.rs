@@ -3,11 +3,12 @@
 
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransformRule {
-    pub replacement: &'static str,
+    pub replacement: Cow<'static, str>,
     /// If true, remove space before the replacement (e.g., punctuation)
     pub attach_to_prev: bool,
     /// If true, don't add space before opening (for quotes/brackets that enclose)
@@ -17,18 +18,30 @@ pub struct TransformRule {
 }
 
 impl TransformRule {
-    const fn new(replacement: &'static str, attach_to_prev: bool) -> Self {
+    /// Rule with an owned replacement, for aliases registered at runtime
+    pub(crate) fn custom(replacement: &str, attach_to_prev: bool, is_opening: bool) -> Self {
         Self {
-            replacement,
+            replacement: Cow::Owned(replacement.to_string()),
+            attach_to_prev,
+            is_opening,
+            no_space_after: false,
+        }
+    }
+
+    /// Plain rule; `attach_to_prev` removes the space before the replacement
+    pub const fn new(replacement: &'static str, attach_to_prev: bool) -> Self {
+        Self {
+            replacement: Cow::Borrowed(replacement),
             attach_to_prev,
             is_opening: false,
             no_space_after: false,
         }
     }
 
-    const fn opening(replacement: &'static str) -> Self {
+    /// Opening bracket or quote: the next word attaches without a space
+    pub const fn opening(replacement: &'static str) -> Self {
         Self {
-            replacement,
+            replacement: Cow::Borrowed(replacement),
             attach_to_prev: false,
             is_opening: true,
             no_space_after: false,
@@ -36,9 +49,9 @@ impl TransformRule {
     }
 
     /// Compact: remove space before AND after (for URLs like example.com)
-    const fn compact(replacement: &'static str) -> Self {
+    pub const fn compact(replacement: &'static str) -> Self {
         Self {
-            replacement,
+            replacement: Cow::Borrowed(replacement),
             attach_to_prev: true,
             is_opening: false,
             no_space_after: true,
@@ -46,9 +59,9 @@ impl TransformRule {
     }
 
     /// No space after only (space before is normal) - for CLI flags like "-m"
-    const fn no_space_after(replacement: &'static str) -> Self {
+    pub const fn no_space_after(replacement: &'static str) -> Self {
         Self {
-            replacement,
+            replacement: Cow::Borrowed(replacement),
             attach_to_prev: false,
             is_opening: false,
             no_space_after: true,
@@ -161,6 +174,8 @@ pub static STATIC_MAPPINGS: Lazy<HashMap<&'static str, TransformRule>> = Lazy::n
     map.insert("and and", TransformRule::new("&&", false));
     map.insert("double pipe", TransformRule::new("||", false));
     map.insert("or or", TransformRule::new("||", false));
+    map.insert("logical and", TransformRule::new("&&", false));
+    map.insert("logical or", TransformRule::new("||", false));
 
     // ========================================
     // H2. Programming Symbols (v2 PRD - unambiguous)
@@ -262,11 +277,101 @@ pub static NUMBER_WORDS: Lazy<HashMap<&'static str, i32>> = Lazy::new(|| {
     map
 });
 
+/// Ordinal number words ("first" → 1, "twentieth" → 20)
+///
+/// Like `NUMBER_WORDS`, these only convert after a trigger (or "the").
+pub static ORDINAL_WORDS: Lazy<HashMap<&'static str, i32>> = Lazy::new(|| {
+    let mut map = HashMap::with_capacity(32);
+    map.insert("first", 1);
+    map.insert("second", 2);
+    map.insert("third", 3);
+    map.insert("fourth", 4);
+    map.insert("fifth", 5);
+    map.insert("sixth", 6);
+    map.insert("seventh", 7);
+    map.insert("eighth", 8);
+    map.insert("ninth", 9);
+    map.insert("tenth", 10);
+    map.insert("eleventh", 11);
+    map.insert("twelfth", 12);
+    map.insert("thirteenth", 13);
+    map.insert("fourteenth", 14);
+    map.insert("fifteenth", 15);
+    map.insert("sixteenth", 16);
+    map.insert("seventeenth", 17);
+    map.insert("eighteenth", 18);
+    map.insert("nineteenth", 19);
+    map.insert("twentieth", 20);
+    map.insert("thirtieth", 30);
+    map.insert("fortieth", 40);
+    map.insert("fiftieth", 50);
+    map.insert("sixtieth", 60);
+    map.insert("seventieth", 70);
+    map.insert("eightieth", 80);
+    map.insert("ninetieth", 90);
+    map.insert("hundredth", 100);
+    map
+});
+
+/// Month names in calendar order, for spoken dates
+pub const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// NATO phonetic alphabet words (opt-in), including common spelling
+/// variants and the radiotelephony "niner"
+pub static NATO_PHONETIC: Lazy<HashMap<&'static str, char>> = Lazy::new(|| {
+    let mut map = HashMap::with_capacity(32);
+    map.insert("alpha", 'A');
+    map.insert("alfa", 'A');
+    map.insert("bravo", 'B');
+    map.insert("charlie", 'C');
+    map.insert("delta", 'D');
+    map.insert("echo", 'E');
+    map.insert("foxtrot", 'F');
+    map.insert("golf", 'G');
+    map.insert("hotel", 'H');
+    map.insert("india", 'I');
+    map.insert("juliet", 'J');
+    map.insert("juliett", 'J');
+    map.insert("kilo", 'K');
+    map.insert("lima", 'L');
+    map.insert("mike", 'M');
+    map.insert("november", 'N');
+    map.insert("oscar", 'O');
+    map.insert("papa", 'P');
+    map.insert("quebec", 'Q');
+    map.insert("romeo", 'R');
+    map.insert("sierra", 'S');
+    map.insert("tango", 'T');
+    map.insert("uniform", 'U');
+    map.insert("victor", 'V');
+    map.insert("whiskey", 'W');
+    map.insert("whisky", 'W');
+    map.insert("xray", 'X');
+    map.insert("x-ray", 'X');
+    map.insert("yankee", 'Y');
+    map.insert("zulu", 'Z');
+    map.insert("niner", '9');
+    map
+});
+
 /// Contextual number triggers that keep the prefix word (v2)
 /// "line forty two" → "line 42", "version two" → "version 2"
 pub static CONTEXTUAL_NUMBER_TRIGGERS: Lazy<HashMap<&'static str, &'static str>> =
     Lazy::new(|| {
-        let mut map = HashMap::with_capacity(8);
+        let mut map = HashMap::with_capacity(16);
         map.insert("line", "line");
         map.insert("version", "version");
         map.insert("step", "step");
@@ -274,5 +379,26 @@ pub static CONTEXTUAL_NUMBER_TRIGGERS: Lazy<HashMap<&'static str, &'static str>>
         map.insert("error", "error");
         map.insert("port", "port");
         map.insert("release", "release");
+        map.insert("temperature", "temperature");
         map
     });
+
+/// Placeholder in snippet templates marking where the cursor should go
+pub const SNIPPET_CURSOR: &str = "{cursor}";
+
+/// Opt-in code snippet commands (multi-word phrase → template)
+///
+/// Kept separate from `STATIC_MAPPINGS` so single-symbol rules are never
+/// shadowed; phrases must not collide with any static mapping key.
+pub static SNIPPETS: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    let mut map = HashMap::with_capacity(16);
+    map.insert("arrow function", "() => { {cursor} }");
+    map.insert("if statement", "if ({cursor}) {}");
+    map.insert("for loop", "for ({cursor}) {}");
+    map.insert("while loop", "while ({cursor}) {}");
+    map.insert("try catch", "try { {cursor} } catch (e) {}");
+    map.insert("console log", "console.log({cursor})");
+    map.insert("main function", "fn main() { {cursor} }");
+    map.insert("test function", "#[test]\nfn {cursor}() {}");
+    map
+});
@@ -2,6 +2,82 @@
 //!
 //! Determines when to add spaces before/after replacements based on context.
 
+use serde::{Deserialize, Serialize};
+
+/// Spacing conventions applied when rules emit brackets
+///
+/// The default reproduces the built-in heuristics: "func(x)", "arr[i]" and
+/// "x = [1]".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpacingPolicy {
+    /// Always put a space before "(", even after an identifier
+    /// ("func (x)" instead of "func(x)")
+    pub space_before_paren: bool,
+    /// Attach "[", "{" and "<" to the preceding word ("arr[i]", "Vec<T>")
+    pub attach_brackets_to_identifiers: bool,
+    /// Keep a space between an operator and a following bracket
+    /// ("x = [1]" instead of "x =[1]")
+    pub space_after_operator: bool,
+    /// Attach the word after "hyphen" ("git commit -m"); when off it keeps
+    /// regular spacing ("git commit - m")
+    pub collapse_operator_spaces: bool,
+    /// Put a space after "(" ("( x + y)" instead of "(x + y)")
+    pub space_after_opening_paren: bool,
+}
+
+/// Spacing options taken by [`transform_with_config`](crate::transform_with_config)
+pub type SpacingConfig = SpacingPolicy;
+
+impl Default for SpacingPolicy {
+    fn default() -> Self {
+        Self {
+            space_before_paren: false,
+            attach_brackets_to_identifiers: true,
+            space_after_operator: true,
+            collapse_operator_spaces: true,
+            space_after_opening_paren: false,
+        }
+    }
+}
+
+impl SpacingPolicy {
+    /// Create a policy with the default conventions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Always space before "("
+    pub fn with_space_before_paren(mut self, enabled: bool) -> Self {
+        self.space_before_paren = enabled;
+        self
+    }
+
+    /// Builder: Attach "[", "{" and "<" to the preceding word
+    pub fn with_attach_brackets_to_identifiers(mut self, enabled: bool) -> Self {
+        self.attach_brackets_to_identifiers = enabled;
+        self
+    }
+
+    /// Builder: Keep a space between an operator and a following bracket
+    pub fn with_space_after_operator(mut self, enabled: bool) -> Self {
+        self.space_after_operator = enabled;
+        self
+    }
+
+    /// Builder: Attach the word following a spaceless operator ("-m")
+    pub fn with_collapse_operator_spaces(mut self, enabled: bool) -> Self {
+        self.collapse_operator_spaces = enabled;
+        self
+    }
+
+    /// Builder: Put a space after "("
+    pub fn with_space_after_opening_paren(mut self, enabled: bool) -> Self {
+        self.space_after_opening_paren = enabled;
+        self
+    }
+}
+
 #[derive(Debug, PartialEq)]
 #[allow(dead_code)]
 pub enum SpacingContext {
@@ -62,6 +138,28 @@ impl SpacingContext {
     }
 }
 
+/// Collapse runs of spaces to a single space, leaving `<KEY:...>` tokens untouched
+pub(crate) fn collapse_spaces(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("<KEY:") {
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        if !(c == ' ' && out.ends_with(' ')) {
+            out.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +200,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_collapse_spaces_skips_key_tokens() {
+        assert_eq!(collapse_spaces("a  b   c"), "a b c");
+        assert_eq!(
+            collapse_spaces("x  <KEY:a  b>  y\n\n"),
+            "x <KEY:a  b> y\n\n"
+        );
+        assert_eq!(
+            collapse_spaces("unterminated <KEY:  "),
+            "unterminated <KEY:  "
+        );
+    }
+
     #[test]
     fn test_quotes() {
         assert_eq!(
@@ -0,0 +1,405 @@
+//! Incremental transform over a stream of words
+//!
+//! `StreamingTransform` runs the same loop as `transform()` but holds back the
+//! last few words until enough lookahead has arrived to match multi-word
+//! patterns. Open-ended runs (numbers, phone numbers, dates, NATO words) and
+//! unclosed literal blocks are held back as long as they reach the last word
+//! received. Concatenating every chunk it emits (including the final
+//! `flush()`) yields exactly `transform()` of the whole input.
+
+use crate::{
+    lookup_words, scan_words, spacing, QuoteState, RuntimeRules, ScanState, TransformAnalysis,
+    TransformOptions, Transformer, MAX_STEP_WORDS,
+};
+
+/// Incremental word-by-word transformer
+#[derive(Default)]
+pub struct StreamingTransform {
+    options: TransformOptions,
+    /// Aliases, mappings and lookahead taken from a `Transformer`
+    rules: RuntimeRules,
+    /// Words received but not yet consumed by the transform loop
+    pending: Vec<String>,
+    state: ScanState,
+    /// Byte offset into `state.result` of output not yet emitted
+    emitted: usize,
+}
+
+impl StreamingTransform {
+    /// Create a streaming transform with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Use explicit transform options
+    pub fn with_options(mut self, options: TransformOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Create a streaming transform with the options, aliases, mappings and
+    /// max lookahead of `transformer`
+    ///
+    /// Later changes to `transformer` do not carry over.
+    pub fn from_transformer(transformer: &Transformer) -> Self {
+        Self {
+            options: transformer.options().clone(),
+            rules: transformer.rules().clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Feed one or more whitespace-separated words.
+    ///
+    /// Returns newly finalized output, if any.
+    pub fn push(&mut self, text: &str) -> Option<String> {
+        self.pending
+            .extend(text.split_whitespace().map(str::to_string));
+
+        // A phrase starting at a step may span `max_pattern_words` words
+        let lookahead = MAX_STEP_WORDS.max(self.rules.max_pattern_words);
+        if self.pending.len() <= lookahead {
+            return None;
+        }
+
+        let limit = self.pending.len() - lookahead;
+        self.scan(limit);
+
+        // Trailing spaces may still be removed by later punctuation
+        let end = self.state.result.trim_end_matches(' ').len();
+        self.take_output(end)
+    }
+
+    /// Transform all remaining words and return the final output, if any.
+    ///
+    /// Resets the transformer so it can be reused for a new stream.
+    pub fn flush(&mut self) -> Option<String> {
+        self.scan(self.pending.len());
+
+        let output = self.take_output(self.state.result.len());
+        *self = Self {
+            options: std::mem::take(&mut self.options),
+            rules: std::mem::take(&mut self.rules),
+            ..Self::default()
+        };
+        output
+    }
+
+    /// Number of words waiting for lookahead
+    pub fn pending_words(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn scan(&mut self, limit: usize) {
+        let words: Vec<&str> = self.pending.iter().map(String::as_str).collect();
+        let words_lower = lookup_words(&words, &self.options);
+
+        let consumed = scan_words(
+            &words,
+            &words_lower,
+            limit,
+            &mut self.state,
+            &self.options,
+            &self.rules,
+            &mut TransformAnalysis::default(),
+        );
+        self.pending.drain(..consumed);
+    }
+
+    /// Emit `state.result[emitted..end]` and drop emitted output, keeping the
+    /// last character the spacing rules look at
+    fn take_output(&mut self, end: usize) -> Option<String> {
+        if end <= self.emitted {
+            return None;
+        }
+
+        let mut chunk = self.state.result[self.emitted..end].to_string();
+        if self.options.collapse_whitespace {
+            chunk = spacing::collapse_spaces(&chunk);
+        }
+
+        let last_char_len = self.state.result[..end]
+            .chars()
+            .next_back()
+            .map_or(0, char::len_utf8);
+        let keep_from = end - last_char_len;
+        self.state.result.drain(..keep_from);
+        self.emitted = end - keep_from;
+
+        Some(chunk)
+    }
+}
+
+/// Chunk-at-a-time transformer for utterance-level streaming
+///
+/// Unlike [`StreamingTransform`], every `push` transforms its whole chunk
+/// right away instead of holding words back for lookahead, so multi-word
+/// patterns must not be split across chunks. Quote state and the spacing
+/// state left by the last rule carry over, so "quote hello" followed by
+/// "world quote" closes the quote opened in the first chunk.
+#[derive(Default)]
+pub struct StreamingTransformer {
+    inner: StreamingTransform,
+}
+
+impl StreamingTransformer {
+    /// Create a streaming transformer with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Use explicit transform options
+    pub fn with_options(mut self, options: TransformOptions) -> Self {
+        self.inner.options = options;
+        self
+    }
+
+    /// Create a streaming transformer with the options, aliases, mappings
+    /// and max lookahead of `transformer`
+    ///
+    /// Later changes to `transformer` do not carry over.
+    pub fn from_transformer(transformer: &Transformer) -> Self {
+        Self {
+            inner: StreamingTransform::from_transformer(transformer),
+        }
+    }
+
+    /// Transform one chunk, continuing from the state left by earlier chunks
+    ///
+    /// Trailing spaces are held back until the next chunk, since it may
+    /// start with punctuation that removes them.
+    pub fn push(&mut self, text: &str) -> String {
+        let inner = &mut self.inner;
+        inner
+            .pending
+            .extend(text.split_whitespace().map(str::to_string));
+        inner.scan(inner.pending.len());
+
+        let end = inner.state.result.trim_end_matches(' ').len();
+        inner.take_output(end).unwrap_or_default()
+    }
+
+    /// Quote state after the last chunk
+    pub fn quote_state(&self) -> QuoteState {
+        self.inner.state.quote_state
+    }
+
+    /// Clear all carried state so the next chunk starts a new stream
+    pub fn reset(&mut self) {
+        self.inner = StreamingTransform {
+            options: std::mem::take(&mut self.inner.options),
+            rules: std::mem::take(&mut self.inner.rules),
+            ..StreamingTransform::default()
+        };
+    }
+}
+
+/// Transform an async stream of words, flushing when the input ends.
+///
+/// Each input item may hold one or more whitespace-separated words; each
+/// output item is a chunk of finalized transform output.
+#[cfg(feature = "stream")]
+pub fn transform_stream<S>(input: S) -> impl futures::Stream<Item = String>
+where
+    S: futures::Stream<Item = String>,
+{
+    use futures::StreamExt;
+
+    futures::stream::unfold(
+        (Box::pin(input), StreamingTransform::new(), false),
+        |(mut input, mut core, done)| async move {
+            if done {
+                return None;
+            }
+
+            while let Some(words) = input.next().await {
+                if let Some(chunk) = core.push(&words) {
+                    return Some((chunk, (input, core, false)));
+                }
+            }
+
+            core.flush().map(|chunk| (chunk, (input, core, true)))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transform, transform_with_options, DateFormat, PhoneFormat, UnitTable};
+
+    fn stream_words(input: &str) -> String {
+        stream_words_with_options(input, &TransformOptions::default())
+    }
+
+    fn stream_words_with_options(input: &str, options: &TransformOptions) -> String {
+        let mut streaming = StreamingTransform::new().with_options(options.clone());
+        let mut output = String::new();
+        for word in input.split_whitespace() {
+            output.extend(streaming.push(word));
+        }
+        output.extend(streaming.flush());
+        output
+    }
+
+    #[test]
+    fn test_streaming_matches_transform() {
+        let inputs = [
+            "Hello comma world period",
+            "x equals sign y plus sign z period new line done",
+            "quote hello world quote and open paren x close paren question mark",
+            "go to line forty two then say comma and the word open paren here",
+            "hello backspace backspace world exclamation mark",
+        ];
+
+        for input in inputs {
+            assert_eq!(stream_words(input), transform(input), "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_transform_for_long_runs() {
+        let options = TransformOptions::new()
+            .with_date_format(DateFormat::MonthDayYear)
+            .with_spoken_decimals(true)
+            .with_nato_phonetic(true);
+        let inputs = [
+            "meet on september twenty third twenty twenty four at noon period",
+            "pi is three point one four one five nine two six five period",
+            "code alpha bravo charlie delta echo foxtrot golf hotel india juliett done",
+            "literal open paren comma close paren end literal",
+            "begin literal one comma two comma three comma four end literal period",
+            "literal comma then a sentence long enough to pass the lookahead period",
+        ];
+
+        for input in inputs {
+            let expected = transform_with_options(input, &options);
+            assert_eq!(
+                stream_words_with_options(input, &options),
+                expected,
+                "input: {:?}",
+                input
+            );
+        }
+        assert_eq!(
+            transform_with_options(inputs[0], &options),
+            "meet on September 23, 2024 at noon."
+        );
+        assert_eq!(
+            transform_with_options(inputs[3], &options),
+            "open paren comma close paren"
+        );
+
+        let units = TransformOptions::new().with_units(UnitTable::default());
+        let input = "it weighs three point one four one five nine kilograms ok";
+        assert_eq!(
+            stream_words_with_options(input, &units),
+            "it weighs 3.14159 kg ok"
+        );
+    }
+
+    #[test]
+    fn test_streaming_formats_phone_numbers() {
+        let options = TransformOptions::new().with_phone_format(PhoneFormat::new());
+        let inputs = [
+            (
+                "call five five five one two three four now",
+                "call 555-1234 now",
+            ),
+            (
+                "phone two oh two five five five one two three four ok",
+                "phone 202-555-1234 ok",
+            ),
+            ("call five five five one two three four", "call 555-1234"),
+        ];
+
+        for (input, expected) in inputs {
+            assert_eq!(transform_with_options(input, &options), expected);
+            assert_eq!(stream_words_with_options(input, &options), expected);
+        }
+    }
+
+    #[test]
+    fn test_streaming_emits_before_flush() {
+        let mut streaming = StreamingTransform::new();
+        let mut early = String::new();
+        for word in "one comma two comma three comma four comma five comma six".split(' ') {
+            early.extend(streaming.push(word));
+        }
+
+        assert!(early.starts_with("one,"));
+        assert!(streaming.pending_words() <= MAX_STEP_WORDS);
+
+        let rest = streaming.flush().unwrap();
+        assert_eq!(
+            early + &rest,
+            transform("one comma two comma three comma four comma five comma six")
+        );
+        assert_eq!(streaming.pending_words(), 0);
+    }
+
+    #[test]
+    fn test_streaming_transformer_carries_state() {
+        let mut streaming = StreamingTransformer::new();
+        assert_eq!(streaming.push("quote hello"), "\"hello");
+        assert_eq!(streaming.push("world quote"), " world\"");
+        assert!(streaming.quote_state().is_balanced());
+
+        // A backtick opened in one chunk is closed two chunks later
+        streaming.reset();
+        let mut output = streaming.push("run backtick ls");
+        assert!(!streaming.quote_state().is_balanced());
+        output += &streaming.push("hyphen");
+        output += &streaming.push("la backtick now");
+        assert!(streaming.quote_state().is_balanced());
+        assert!(output.starts_with("run `ls -la`"));
+        assert_eq!(output, transform("run backtick ls hyphen la backtick now"));
+
+        assert_eq!(streaming.push("quote open"), " \"open");
+        assert!(!streaming.quote_state().is_balanced());
+        streaming.reset();
+        assert!(streaming.quote_state().is_balanced());
+        assert_eq!(streaming.push("quote again"), "\"again");
+    }
+
+    #[test]
+    fn test_streaming_uses_transformer_rules() {
+        let mut transformer = Transformer::new().with_max_lookahead(7);
+        transformer.add_alias("splat", "*", false, false);
+        transformer.add_alias("one two three four five six seven", "1-7", false, false);
+        let input = "a splat b then zero one two three four five six seven end";
+        let expected = transformer.transform(input);
+        assert_eq!(expected, "a * b then zero 1-7 end");
+
+        let mut streaming = StreamingTransform::from_transformer(&transformer);
+        let mut output = String::new();
+        for word in input.split_whitespace() {
+            output.extend(streaming.push(word));
+        }
+        output.extend(streaming.flush());
+        assert_eq!(output, expected);
+
+        // Rules survive a flush
+        assert_eq!(streaming.push("x splat y"), None);
+        assert_eq!(streaming.flush().unwrap(), "x * y");
+
+        let mut chunks = StreamingTransformer::from_transformer(&transformer);
+        assert_eq!(chunks.push("a splat b"), "a * b");
+        chunks.reset();
+        assert_eq!(chunks.push("splat"), "*");
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn test_transform_stream() {
+        use futures::StreamExt;
+
+        let words = "open paren x plus sign y close paren period done"
+            .split(' ')
+            .map(str::to_string);
+        let chunks: Vec<String> =
+            futures::executor::block_on(transform_stream(futures::stream::iter(words)).collect());
+
+        assert_eq!(chunks.concat(), "(x + y). done");
+    }
+}
@@ -0,0 +1,247 @@
+//! Stateful transformer for multi-segment documents
+//!
+//! `transform()` starts every call with all quotes closed. `Transformer`
+//! carries quote state from one call to the next so a document can be fed
+//! segment by segment, and lets the caller inspect or reset it (e.g. at
+//! paragraph boundaries, so one missing closing quote doesn't flip every
+//! later quote).
+
+use crate::{
+    run_transform, FuzzyTriggers, QuoteState, RuntimeRules, TransformOptions, TransformRule,
+};
+
+/// Transformer that keeps quote state between calls
+#[derive(Debug, Clone, Default)]
+pub struct Transformer {
+    options: TransformOptions,
+    quote_state: QuoteState,
+    /// Aliases and other rules added at runtime
+    rules: RuntimeRules,
+}
+
+impl Transformer {
+    /// Create a transformer with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: Use explicit transform options
+    pub fn with_options(mut self, options: TransformOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Builder: Let near-miss contextual trigger words still fire
+    ///
+    /// A word followed by a number that is not itself a trigger ("lion
+    /// forty two") is fuzzy-matched against the trigger words, and the
+    /// closest one is emitted if its similarity (0.0-1.0) is at least
+    /// `threshold` ("line 42").
+    pub fn with_fuzzy_triggers(mut self, threshold: f64) -> Self {
+        self.rules.fuzzy_triggers = Some(FuzzyTriggers::new(threshold.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Builder: Probe phrases of up to `words` words (default 4)
+    ///
+    /// Raise it to register longer aliases; lower it to skip the longer
+    /// lookups on latency-critical paths. Values below 1 are treated as 1,
+    /// which disables multi-word phrases entirely.
+    pub fn with_max_lookahead(mut self, words: usize) -> Self {
+        self.rules.max_pattern_words = words.max(1);
+        self
+    }
+
+    /// Transform one segment, continuing from the current quote state
+    pub fn transform(&mut self, text: &str) -> String {
+        run_transform(text, &self.options, &self.rules, &mut self.quote_state).output
+    }
+
+    /// Register a spoken alias for a symbol (e.g. "splat" → "*")
+    ///
+    /// Aliases take precedence over built-in rules with the same phrase and
+    /// take part in longest-match-first lookup, so phrases of up to
+    /// `with_max_lookahead` words (default 4) work. `attach_to_prev` removes
+    /// the space before the replacement (punctuation); `is_opening` gives it
+    /// opening bracket/quote spacing.
+    pub fn add_alias(
+        &mut self,
+        phrase: &str,
+        replacement: &str,
+        attach_to_prev: bool,
+        is_opening: bool,
+    ) {
+        self.add_mapping(
+            phrase,
+            TransformRule::custom(replacement, attach_to_prev, is_opening),
+        );
+    }
+
+    /// Register a rule for a spoken phrase (e.g. "smiley" → ":)")
+    ///
+    /// Like `add_alias`, but takes a full [`TransformRule`] so every spacing
+    /// flag can be set. The phrase is matched case-insensitively and takes
+    /// precedence over a built-in rule for the same phrase.
+    pub fn add_mapping(&mut self, phrase: &str, rule: TransformRule) {
+        self.rules.aliases.insert(mapping_key(phrase), rule);
+    }
+
+    /// Remove a phrase registered with `add_mapping` or `add_alias`
+    ///
+    /// Returns the removed rule; a built-in rule for the same phrase applies
+    /// again afterwards.
+    pub fn remove_mapping(&mut self, phrase: &str) -> Option<TransformRule> {
+        self.rules.aliases.remove(&mapping_key(phrase))
+    }
+
+    /// Quote state after the last transformed segment
+    pub fn quote_state(&self) -> QuoteState {
+        self.quote_state
+    }
+
+    /// Close all open quotes so the next segment starts fresh
+    pub fn reset_quote_state(&mut self) {
+        self.quote_state = QuoteState::default();
+    }
+
+    /// Get the transform options
+    pub fn options(&self) -> &TransformOptions {
+        &self.options
+    }
+
+    /// Aliases, mappings and lookahead settings added to this transformer
+    pub(crate) fn rules(&self) -> &RuntimeRules {
+        &self.rules
+    }
+}
+
+/// Lookup key for a phrase: lowercase words joined by single spaces
+fn mapping_key(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform;
+
+    #[test]
+    fn test_quote_state_carries_across_segments() {
+        let mut transformer = Transformer::new();
+
+        assert_eq!(transformer.transform("quote hello"), "\"hello");
+        assert!(transformer.quote_state().double_quote_open);
+        assert!(!transformer.quote_state().is_balanced());
+
+        assert_eq!(transformer.transform("world quote"), "world\"");
+        assert!(transformer.quote_state().is_balanced());
+    }
+
+    #[test]
+    fn test_aliases() {
+        let mut transformer = Transformer::new();
+        transformer.add_alias("splat", "*", false, false);
+        transformer.add_alias("bang", "!", true, false);
+        transformer.add_alias("Pipe  Forward", "|>", false, false);
+
+        assert_eq!(transformer.transform("a splat b"), "a * b");
+        assert_eq!(transformer.transform("wow bang"), "wow!");
+        assert_eq!(transformer.transform("x pipe forward f"), "x |> f");
+
+        // Aliases are per instance
+        assert_eq!(transform("wow bang"), "wow bang");
+        assert_eq!(transform("a splat b"), "a ... b");
+    }
+
+    #[test]
+    fn test_add_and_remove_mapping() {
+        let mut transformer = Transformer::new();
+        transformer.add_mapping("smiley", TransformRule::new(":)", false));
+        transformer.add_mapping("Comma", TransformRule::new(" ,", true));
+        transformer.add_mapping("question mark twice", TransformRule::new("??", true));
+
+        assert_eq!(transformer.transform("nice smiley"), "nice :)");
+        assert_eq!(transformer.transform("a comma b"), "a , b");
+        // Longer user phrases win over built-in prefixes
+        assert_eq!(transformer.transform("what question mark twice"), "what??");
+        assert_eq!(transformer.transform("what question mark"), "what?");
+
+        assert!(transformer.remove_mapping("COMMA").is_some());
+        assert!(transformer.remove_mapping("comma").is_none());
+        assert_eq!(transformer.transform("a comma b"), "a, b");
+        assert_eq!(transform("nice smiley"), "nice smiley");
+    }
+
+    #[test]
+    fn test_alias_overrides_static_rule() {
+        let mut transformer = Transformer::new();
+        transformer.add_alias("open paren", "⟨", false, true);
+        transformer.add_alias("literal", "lit", false, false);
+
+        assert_eq!(transformer.transform("f open paren x"), "f⟨x");
+        // Escape triggers are not rules and still apply
+        assert_eq!(transformer.transform("literal comma"), "comma");
+    }
+
+    #[test]
+    fn test_max_lookahead() {
+        let mut default = Transformer::new();
+        default.add_alias("end of the code block", "```", false, false);
+        assert_eq!(
+            default.transform("x end of the code block"),
+            "x end of the code block"
+        );
+
+        let mut long = Transformer::new().with_max_lookahead(5);
+        long.add_alias("end of the code block", "```", false, false);
+        assert_eq!(long.transform("x end of the code block"), "x ```");
+        assert_eq!(long.transform("a question mark"), "a?");
+
+        let mut capped = Transformer::new().with_max_lookahead(2);
+        capped.add_alias("three word phrase", "3wp", false, false);
+        assert_eq!(
+            capped.transform("a three word phrase"),
+            "a three word phrase"
+        );
+        assert_eq!(capped.transform("a question mark"), "a?");
+        assert_eq!(capped.transform("x open paren y"), "x(y");
+    }
+
+    #[test]
+    fn test_fuzzy_triggers() {
+        let mut fuzzy = Transformer::new().with_fuzzy_triggers(0.8);
+        assert_eq!(fuzzy.transform("lion forty two"), "line 42");
+        assert_eq!(fuzzy.transform("go to line forty two"), "go to line 42");
+        // Only words followed by a number are candidates
+        assert_eq!(fuzzy.transform("the lion sleeps"), "the lion sleeps");
+        // Distant words are not snapped to a trigger
+        assert_eq!(fuzzy.transform("apple two"), "apple two");
+
+        let mut exact = Transformer::new();
+        assert_eq!(exact.transform("lion forty two"), "lion forty two");
+    }
+
+    #[test]
+    fn test_reset_quote_state_fixes_pairing() {
+        let mut transformer = Transformer::new();
+
+        // Unbalanced segment: the closing quote was never dictated
+        transformer.transform("she said quote hi");
+        assert!(transformer.quote_state().double_quote_open);
+
+        // Without a reset the next segment's quotes pair up backwards
+        let mut unreset = transformer.clone();
+        assert_ne!(
+            unreset.transform("quote ok quote"),
+            transform("quote ok quote")
+        );
+
+        transformer.reset_quote_state();
+        assert_eq!(transformer.quote_state(), QuoteState::default());
+        assert_eq!(transformer.transform("quote ok quote"), "\"ok\"");
+    }
+}
@@ -4,9 +4,10 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// Transform mode for context-aware corrections
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransformMode {
     /// Secretary/dictation mode - natural language
+    #[default]
     Secretary,
     /// Code/programming mode - technical terms
     Code,
@@ -18,12 +19,6 @@ pub enum TransformMode {
     Minimal,
 }
 
-impl Default for TransformMode {
-    fn default() -> Self {
-        TransformMode::Secretary
-    }
-}
-
 /// Configuration for Text Transform v3
 #[derive(Debug, Clone)]
 pub struct TransformConfig {
@@ -31,12 +26,19 @@ pub struct TransformConfig {
     /// Enable fuzzy temporal matching
     pub fuzzy_enabled: bool,
 
-    /// Confidence threshold for fuzzy matches (0.0-1.0)
-    /// Higher = more strict, only exact matches
-    /// Lower = more lenient, catches more variations
-    /// Recommended: 0.8 (user preference: accuracy > speed)
+    /// Fuzzy match threshold (0.0-1.0): the largest distance, relative to
+    /// pattern length, that still counts as a match
+    /// Higher = more lenient, catches more variations
+    /// Lower = more strict, 0.0 only accepts exact matches
+    /// Recommended: 0.8
     pub fuzzy_threshold: f64,
 
+    /// Hard confidence floor for fuzzy matches (0.0-1.0)
+    /// Inputs whose best fuzzy candidate scores below this pass through
+    /// verbatim instead of being corrected or falling back to static rules.
+    /// 0.0 disables the floor.
+    pub confidence_floor: f64,
+
     /// LRU cache size for fuzzy matching performance
     pub fuzzy_cache_size: usize,
 
@@ -54,6 +56,11 @@ pub struct TransformConfig {
     /// Path to user corrections file (corrections.toml)
     pub corrections_path: Option<PathBuf>,
 
+    /// Path to the JSON file of learned user overrides (optional)
+    /// Loaded by `TransformV3::new` if it exists; written back by
+    /// `TransformV3::flush`, and on drop if the overrides changed.
+    pub learned_corrections_path: Option<PathBuf>,
+
     /// Learn from corrections in real-time
     pub learn_on_correction: bool,
 
@@ -73,6 +80,7 @@ impl Default for TransformConfig {
             // Fuzzy matching
             fuzzy_enabled: true,
             fuzzy_threshold: 0.8, // Balance: catches variations but maintains accuracy
+            confidence_floor: 0.0,
             fuzzy_cache_size: 1000,
             max_pattern_length: 20,
 
@@ -82,6 +90,7 @@ impl Default for TransformConfig {
 
             // User overrides
             corrections_path: None,
+            learned_corrections_path: None,
             learn_on_correction: true,
 
             // Context
@@ -114,6 +123,12 @@ impl TransformConfig {
         self
     }
 
+    /// Builder: Set the fuzzy match confidence floor
+    pub fn with_confidence_floor(mut self, floor: f64) -> Self {
+        self.confidence_floor = floor.clamp(0.0, 1.0);
+        self
+    }
+
     /// Builder: Set fuzzy matching enabled/disabled
     pub fn with_fuzzy_enabled(mut self, enabled: bool) -> Self {
         self.fuzzy_enabled = enabled;
@@ -132,6 +147,12 @@ impl TransformConfig {
         self
     }
 
+    /// Builder: Persist learned user overrides to `path`
+    pub fn with_learned_corrections_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.learned_corrections_path = Some(path.into());
+        self
+    }
+
     /// Builder: Set transformation mode
     pub fn with_mode(mut self, mode: TransformMode) -> Self {
         self.mode = mode;
@@ -159,6 +180,13 @@ impl TransformConfig {
             )));
         }
 
+        if !(0.0..=1.0).contains(&self.confidence_floor) {
+            return Err(crate::v3::TransformError::ConfigError(format!(
+                "Invalid confidence_floor: {} (must be 0.0-1.0)",
+                self.confidence_floor
+            )));
+        }
+
         if self.fuzzy_cache_size == 0 {
             return Err(crate::v3::TransformError::ConfigError(
                 "fuzzy_cache_size must be > 0".to_string(),
@@ -207,6 +235,9 @@ mod tests {
 
         let config = TransformConfig::new().with_fuzzy_threshold(-0.5);
         assert_eq!(config.fuzzy_threshold, 0.0);
+
+        let config = TransformConfig::new().with_confidence_floor(2.0);
+        assert_eq!(config.confidence_floor, 1.0);
     }
 
     #[test]
@@ -214,8 +245,10 @@ mod tests {
         let config = TransformConfig::default();
         assert!(config.validate().is_ok());
 
-        let mut invalid = TransformConfig::default();
-        invalid.fuzzy_cache_size = 0;
+        let invalid = TransformConfig {
+            fuzzy_cache_size: 0,
+            ..Default::default()
+        };
         assert!(invalid.validate().is_err());
     }
 }
@@ -2,15 +2,24 @@
 //!
 //! Wraps `TemporalComparator` from the temporal-compare crate to provide
 //! intelligent pattern matching based on historical correction patterns.
+//! Without the `temporal-compare` feature, the Levenshtein distance from
+//! `midstreamer-edit-distance` over the same sliding windows stands in for
+//! DTW.
 
+#[cfg(feature = "temporal-compare")]
 use midstreamer_temporal_compare::TemporalComparator;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
+use crate::rules::STATIC_MAPPINGS;
 use crate::v3::{Result, TransformError};
 
+/// Tokens up to this many characters may be at most one edit away from a
+/// phrase in `fuzzy_lookup`; longer tokens may be two
+const SHORT_TOKEN_LEN: usize = 6;
+
 /// User correction pattern for learning
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorrectionPattern {
@@ -40,26 +49,39 @@ pub struct CorrectionsFile {
 /// Fuzzy matcher that learns from user corrections
 pub struct FuzzyMatcher {
     /// Temporal comparator for DTW-based pattern matching
+    #[cfg(feature = "temporal-compare")]
     comparator: TemporalComparator<u8>,
 
     /// Learned correction patterns (from -> to)
     patterns: HashMap<String, String>,
 
-    /// Confidence threshold for matches (0.0-1.0)
+    /// Maximum normalized distance (distance / length, 0.0-1.0) for a
+    /// match; higher is looser. `fuzzy_lookup` accepts similarities of at
+    /// least `1 - threshold`
     threshold: f64,
 
     /// Maximum pattern length to consider
     max_pattern_length: usize,
+
+    /// Rejected (input, pattern) pairs that must not match again
+    rejected: HashSet<(String, String)>,
 }
 
 impl FuzzyMatcher {
     /// Create a new fuzzy matcher with specified configuration
+    ///
+    /// `cache_size` is ignored without the `temporal-compare` feature.
     pub fn new(cache_size: usize, max_seq_length: usize, threshold: f64) -> Self {
+        #[cfg(not(feature = "temporal-compare"))]
+        let _ = cache_size;
+
         Self {
+            #[cfg(feature = "temporal-compare")]
             comparator: TemporalComparator::new(cache_size, max_seq_length),
             patterns: HashMap::new(),
             threshold: threshold.clamp(0.0, 1.0),
             max_pattern_length: max_seq_length,
+            rejected: HashSet::new(),
         }
     }
 
@@ -107,27 +129,16 @@ impl FuzzyMatcher {
         let mut best_similarity = 0.0;
 
         for (pattern_str, correction) in &self.patterns {
-            if pattern_str.len() > self.max_pattern_length {
+            if self
+                .rejected
+                .contains(&(text.to_string(), pattern_str.clone()))
+            {
                 continue;
             }
 
-            let pattern_bytes: Vec<u8> = pattern_str.bytes().collect();
-
-            // Calculate similarity using find_similar_generic
-            let matches = match self.comparator.find_similar_generic(
-                &text_bytes,
-                &pattern_bytes,
-                self.threshold,
-            ) {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-
-            if matches.is_empty() {
+            let Some(similarity) = self.similarity(&text_bytes, pattern_str) else {
                 continue;
-            }
-
-            let similarity = matches[0].similarity;
+            };
 
             // Similarity is already normalized (0.0 to 1.0)
             if similarity > best_similarity {
@@ -139,6 +150,127 @@ impl FuzzyMatcher {
         best_match
     }
 
+    /// Find the candidate closest to `input`, ignoring learned patterns
+    ///
+    /// Uses the same metric and threshold as `find_match`. Returns the best
+    /// candidate and its similarity; ties go to the earliest candidate.
+    pub fn closest<'a>(&self, input: &str, candidates: &'a [String]) -> Option<(&'a str, f64)> {
+        if input.is_empty() || input.len() > self.max_pattern_length {
+            return None;
+        }
+
+        let input_bytes: Vec<u8> = input.bytes().collect();
+        let mut best: Option<(&'a str, f64)> = None;
+
+        for candidate in candidates {
+            let Some(similarity) = self.similarity(&input_bytes, candidate) else {
+                continue;
+            };
+
+            if best.is_none_or(|(_, best_similarity)| similarity > best_similarity) {
+                best = Some((candidate.as_str(), similarity));
+            }
+        }
+
+        best
+    }
+
+    /// Similarity of `pattern` to the input bytes, if within the threshold
+    #[cfg(feature = "temporal-compare")]
+    fn similarity(&self, text_bytes: &[u8], pattern: &str) -> Option<f64> {
+        if pattern.len() > self.max_pattern_length {
+            return None;
+        }
+
+        let pattern_bytes: Vec<u8> = pattern.bytes().collect();
+
+        // Calculate similarity using find_similar_generic
+        let matches = self
+            .comparator
+            .find_similar_generic(text_bytes, &pattern_bytes, self.threshold)
+            .ok()?;
+
+        matches.first().map(|m| m.similarity)
+    }
+
+    /// Edit-distance fallback for `similarity`
+    ///
+    /// Slides `pattern` over the input like `find_similar_generic` and scores
+    /// the closest window with the same `exp(-distance / 10)` curve.
+    #[cfg(not(feature = "temporal-compare"))]
+    fn similarity(&self, text_bytes: &[u8], pattern: &str) -> Option<f64> {
+        let pattern_bytes = pattern.as_bytes();
+        if pattern_bytes.is_empty()
+            || pattern_bytes.len() > self.max_pattern_length
+            || text_bytes.len() < pattern_bytes.len()
+        {
+            return None;
+        }
+
+        let distance = text_bytes
+            .windows(pattern_bytes.len())
+            .map(|window| midstreamer_edit_distance::distance(window, pattern_bytes))
+            .min()? as f64;
+
+        (distance / pattern_bytes.len() as f64 <= self.threshold).then(|| (-distance / 10.0).exp())
+    }
+
+    /// Closest static phrase to a misrecognized word ("comme" → "comma")
+    ///
+    /// Candidates are the built-in phrase keys, compared by edit distance.
+    /// Tokens of up to six characters may be one edit away, longer ones two,
+    /// so short real words are not snapped to a phrase. Similarity is
+    /// `1 - distance / longer length`; as in `find_match`, the normalized
+    /// distance must stay within the threshold. Ties go to the
+    /// alphabetically first phrase.
+    pub fn fuzzy_lookup(&self, word: &str) -> Option<(String, f64)> {
+        let word: Vec<char> = word.to_lowercase().chars().collect();
+        if word.is_empty() || word.len() > self.max_pattern_length {
+            return None;
+        }
+        let max_distance = if word.len() <= SHORT_TOKEN_LEN { 1 } else { 2 };
+
+        let mut best: Option<(&str, f64)> = None;
+        for &phrase in STATIC_MAPPINGS.keys() {
+            let candidate: Vec<char> = phrase.chars().collect();
+            if candidate.len().abs_diff(word.len()) > max_distance {
+                continue;
+            }
+
+            let distance = midstreamer_edit_distance::distance(&word, &candidate);
+            if distance > max_distance {
+                continue;
+            }
+
+            let similarity = 1.0 - distance as f64 / word.len().max(candidate.len()) as f64;
+            if similarity < 1.0 - self.threshold {
+                continue;
+            }
+
+            let better = best.is_none_or(|(best_phrase, best_similarity)| {
+                similarity > best_similarity
+                    || (similarity == best_similarity && phrase < best_phrase)
+            });
+            if better {
+                best = Some((phrase, similarity));
+            }
+        }
+
+        best.map(|(phrase, similarity)| (phrase.to_string(), similarity))
+    }
+
+    /// Blacklist a pattern for a specific input so it is never suggested again
+    pub fn reject_pair(&mut self, input: &str, pattern: &str) {
+        self.rejected
+            .insert((input.to_string(), pattern.to_string()));
+    }
+
+    /// Check whether an (input, pattern) pair has been rejected
+    pub fn is_rejected(&self, input: &str, pattern: &str) -> bool {
+        self.rejected
+            .contains(&(input.to_string(), pattern.to_string()))
+    }
+
     /// Check if there are any loaded patterns
     pub fn has_patterns(&self) -> bool {
         !self.patterns.is_empty()
@@ -163,6 +295,7 @@ impl FuzzyMatcher {
 
     /// Clear the internal cache
     pub fn clear_cache(&mut self) {
+        #[cfg(feature = "temporal-compare")]
         self.comparator.clear_cache();
     }
 }
@@ -218,6 +351,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_closest_command() {
+        let mut matcher = FuzzyMatcher::new(100, 1000, 0.7);
+        matcher.learn_pattern("comit".to_string(), "commit".to_string());
+
+        let vocabulary: Vec<String> = ["add", "push", "commit", "checkout"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let (best, score) = matcher.closest("commmit", &vocabulary).unwrap();
+        assert_eq!(best, "commit");
+        assert!(score > 0.0 && score < 1.0);
+
+        assert_eq!(matcher.closest("pusj", &vocabulary).unwrap().0, "push");
+        assert_eq!(
+            matcher.closest("checkout", &vocabulary),
+            Some(("checkout", 1.0))
+        );
+        assert_eq!(matcher.closest("xyz", &vocabulary), None);
+
+        // Learned patterns are not candidates
+        assert_eq!(matcher.closest("comit", &[]), None);
+    }
+
+    #[test]
+    fn test_fuzzy_lookup_static_phrases() {
+        let matcher = FuzzyMatcher::new(100, 1000, 0.8);
+
+        let (phrase, similarity) = matcher.fuzzy_lookup("comme").unwrap();
+        assert_eq!(phrase, "comma");
+        assert!((similarity - 0.8).abs() < 1e-9);
+
+        assert_eq!(
+            matcher.fuzzy_lookup("Period").unwrap(),
+            ("period".to_string(), 1.0)
+        );
+        assert_eq!(matcher.fuzzy_lookup("semicolin").unwrap().0, "semicolon");
+
+        // Short tokens get at most one edit
+        assert_eq!(matcher.fuzzy_lookup("kama"), None);
+        assert_eq!(matcher.fuzzy_lookup("hello"), None);
+        assert_eq!(matcher.fuzzy_lookup(""), None);
+
+        // The threshold bounds normalized distance, as in `find_match`
+        let strict = FuzzyMatcher::new(100, 1000, 0.1);
+        assert_eq!(strict.fuzzy_lookup("comme"), None);
+        assert_eq!(strict.fuzzy_lookup("period").unwrap().0, "period");
+
+        // A looser threshold still keeps the edit bound
+        let loose = FuzzyMatcher::new(100, 1000, 1.0);
+        assert_eq!(loose.fuzzy_lookup("kama"), None);
+        assert_eq!(loose.fuzzy_lookup("coma").unwrap().0, "comma");
+    }
+
     #[test]
     fn test_no_match_below_threshold() {
         let mut matcher = FuzzyMatcher::new(100, 1000, 0.9); // High threshold
@@ -263,6 +451,17 @@ count = 3
         assert_eq!(result.unwrap(), 0);
     }
 
+    #[test]
+    fn test_rejected_pair_not_matched() {
+        let mut matcher = FuzzyMatcher::new(100, 1000, 0.7);
+        matcher.learn_pattern("arkon".to_string(), "archon".to_string());
+        assert!(matcher.find_match("arkon").is_some());
+
+        matcher.reject_pair("arkon", "arkon");
+        assert!(matcher.is_rejected("arkon", "arkon"));
+        assert!(matcher.find_match("arkon").is_none());
+    }
+
     #[test]
     fn test_clear_patterns() {
         let mut matcher = FuzzyMatcher::new(100, 1000, 0.8);
@@ -276,7 +475,7 @@ count = 3
 
     #[test]
     fn test_max_pattern_length() {
-        let mut matcher = FuzzyMatcher::new(100, 10, 0.8); // Max length: 10
+        let matcher = FuzzyMatcher::new(100, 10, 0.8); // Max length: 10
 
         // Pattern longer than max should be ignored
         let long_text = "this is a very long text that exceeds the maximum pattern length";
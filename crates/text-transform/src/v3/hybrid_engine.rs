@@ -5,9 +5,28 @@
 //! 2. Fuzzy temporal matching (learned patterns)
 //! 3. Static rules (v2 fallback)
 
-use std::collections::HashMap;
-
-use crate::v3::{FuzzyMatcher, Result, StaticRules, TransformConfig, TransformMode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::v3::{
+    FuzzyMatcher, Result, StaticRules, TransformConfig, TransformError, TransformMode,
+};
+
+/// Version written to saved corrections files
+const CORRECTIONS_FORMAT_VERSION: u32 = 1;
+
+/// On-disk format of saved user overrides
+///
+/// Unknown fields are ignored on load, so files written by newer versions
+/// still load their overrides.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedCorrections {
+    version: u32,
+    #[serde(default)]
+    overrides: BTreeMap<String, String>,
+}
 
 /// User override (highest priority corrections)
 #[derive(Debug, Clone)]
@@ -31,6 +50,9 @@ pub struct TransformV3 {
     /// User overrides (tier 1 - highest priority)
     user_overrides: HashMap<String, String>,
 
+    /// Whether user overrides changed since they were last loaded or saved
+    overrides_dirty: bool,
+
     /// Statistics
     stats: TransformStats,
 }
@@ -88,11 +110,19 @@ impl TransformV3 {
             static_rules.load_rules_file(path)?;
         }
 
+        // Restore overrides learned in earlier sessions. Done before the
+        // engine exists so a file that fails to load is never overwritten.
+        let user_overrides = match config.learned_corrections_path {
+            Some(ref path) if path.exists() => read_corrections(path)?.into_iter().collect(),
+            _ => HashMap::new(),
+        };
+
         Ok(Self {
             config,
             fuzzy_matcher,
             static_rules,
-            user_overrides: HashMap::new(),
+            user_overrides,
+            overrides_dirty: false,
             stats: TransformStats::default(),
         })
     }
@@ -110,7 +140,12 @@ impl TransformV3 {
 
         // Tier 2: Fuzzy temporal matching (learned patterns)
         if let Some(ref matcher) = self.fuzzy_matcher {
-            if let Some((_, correction, _confidence)) = matcher.find_match(text) {
+            if let Some((_, correction, confidence)) = matcher.find_match(text) {
+                // Below the floor the candidate is too distant to trust at all
+                if confidence < self.config.confidence_floor {
+                    return text.to_string();
+                }
+
                 self.stats.successful += 1;
                 self.stats.fuzzy_matches += 1;
 
@@ -156,6 +191,7 @@ impl TransformV3 {
     /// Add a user override correction
     pub fn add_user_override(&mut self, from: String, to: String) {
         self.user_overrides.insert(from, to);
+        self.overrides_dirty = true;
     }
 
     /// Learn a correction pattern (adds to fuzzy matcher)
@@ -170,6 +206,66 @@ impl TransformV3 {
         }
     }
 
+    /// Feed back whether the user accepted the fuzzy correction for `input`
+    ///
+    /// An accepted correction is promoted to a user override (tier 1); a
+    /// rejected one is blacklisted for this input so the fuzzy matcher never
+    /// suggests it again. Returns `false` if no fuzzy match exists for `input`.
+    pub fn confirm(&mut self, input: &str, accepted: bool) -> bool {
+        let Some(ref mut matcher) = self.fuzzy_matcher else {
+            return false;
+        };
+        let Some((pattern, correction, _confidence)) = matcher.find_match(input) else {
+            return false;
+        };
+
+        if accepted {
+            self.add_user_override(input.to_string(), correction);
+        } else {
+            matcher.reject_pair(input, &pattern);
+            if self.user_overrides.get(input) == Some(&correction) {
+                self.user_overrides.remove(input);
+                self.overrides_dirty = true;
+            }
+        }
+
+        true
+    }
+
+    /// Save user overrides to `path` as versioned JSON
+    pub fn save_corrections(&self, path: &Path) -> Result<()> {
+        let saved = SavedCorrections {
+            version: CORRECTIONS_FORMAT_VERSION,
+            overrides: self
+                .user_overrides
+                .iter()
+                .map(|(from, to)| (from.clone(), to.clone()))
+                .collect(),
+        };
+        let json = serde_json::to_string_pretty(&saved)
+            .map_err(|e| TransformError::SerializationError(e.to_string()))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load user overrides saved by `save_corrections`
+    ///
+    /// Loaded overrides are added to (and replace) the current ones.
+    pub fn load_corrections(&mut self, path: &Path) -> Result<()> {
+        self.user_overrides.extend(read_corrections(path)?);
+        self.overrides_dirty = true;
+        Ok(())
+    }
+
+    /// Save user overrides to `learned_corrections_path`, if configured
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(ref path) = self.config.learned_corrections_path {
+            self.save_corrections(path)?;
+            self.overrides_dirty = false;
+        }
+        Ok(())
+    }
+
     /// Change transformation mode
     pub fn set_mode(&mut self, mode: TransformMode) {
         self.config.mode = mode;
@@ -234,6 +330,26 @@ impl TransformV3 {
     }
 }
 
+impl Drop for TransformV3 {
+    fn drop(&mut self) {
+        // Only write back changes, so an engine that learned nothing never
+        // overwrites a file shared with another engine. Errors cannot be
+        // reported from drop; call `flush` to handle them.
+        if self.overrides_dirty {
+            let _ = self.flush();
+        }
+    }
+}
+
+/// Read user overrides saved by `TransformV3::save_corrections`
+fn read_corrections(path: &Path) -> Result<BTreeMap<String, String>> {
+    let json = fs::read_to_string(path)?;
+    let saved: SavedCorrections = serde_json::from_str(&json).map_err(|e| {
+        TransformError::PatternLoadError(format!("Failed to parse {}: {}", path.display(), e))
+    })?;
+    Ok(saved.overrides)
+}
+
 impl TransformStats {
     /// Get tier breakdown as percentages
     pub fn tier_breakdown(&self) -> (f64, f64, f64) {
@@ -314,6 +430,147 @@ mod tests {
         assert_eq!(result, "archon");
     }
 
+    #[test]
+    fn test_save_and_load_corrections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learned.json");
+
+        let mut transformer = TransformV3::new(TransformConfig::default()).unwrap();
+        transformer.learn_correction("arkon".to_string(), "archon".to_string());
+        transformer.save_corrections(&path).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], 1);
+        assert_eq!(saved["overrides"]["arkon"], "archon");
+
+        let mut restored = TransformV3::new(TransformConfig::v2_compatible()).unwrap();
+        restored.load_corrections(&path).unwrap();
+        assert_eq!(restored.transform("arkon"), "archon");
+
+        // Newer files with extra fields still load
+        std::fs::write(
+            &path,
+            r#"{"version": 2, "overrides": {"teh": "the"}, "weights": {}}"#,
+        )
+        .unwrap();
+        restored.load_corrections(&path).unwrap();
+        assert_eq!(restored.transform("teh"), "the");
+
+        assert!(restored
+            .load_corrections(&dir.path().join("missing.json"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_learned_corrections_path_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learned.json");
+        let config = TransformConfig::default().with_learned_corrections_path(&path);
+
+        {
+            let mut transformer = TransformV3::new(config.clone()).unwrap();
+            assert_eq!(transformer.user_override_count(), 0);
+            transformer.learn_correction("arkon".to_string(), "archon".to_string());
+            transformer.flush().unwrap();
+            assert!(path.exists());
+
+            // Saved again on drop
+            transformer.learn_correction("teh".to_string(), "the".to_string());
+        }
+
+        let mut transformer = TransformV3::new(config).unwrap();
+        assert_eq!(transformer.user_override_count(), 2);
+        assert_eq!(transformer.transform("teh"), "the");
+        assert_eq!(transformer.stats().user_override_matches, 1);
+    }
+
+    #[test]
+    fn test_malformed_learned_corrections_left_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("learned.json");
+        let malformed = r#"{"version": 1, "overrides": {"teh": "the",}}"#;
+        std::fs::write(&path, malformed).unwrap();
+
+        let config = TransformConfig::default().with_learned_corrections_path(&path);
+        assert!(TransformV3::new(config.clone()).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), malformed);
+
+        // An engine that changed nothing does not write on drop
+        let valid = r#"{"version": 1, "overrides": {"teh": "the"}}"#;
+        std::fs::write(&path, valid).unwrap();
+        drop(TransformV3::new(config).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), valid);
+    }
+
+    #[test]
+    fn test_confirm_promotes_to_override() {
+        let config = TransformConfig {
+            learn_on_correction: false,
+            ..TransformConfig::default()
+        };
+        let mut transformer = TransformV3::new(config).unwrap();
+        transformer.learn_correction("arkon".to_string(), "archon".to_string());
+        assert_eq!(transformer.user_override_count(), 0);
+
+        assert!(transformer.confirm("arkon", true));
+        assert_eq!(transformer.user_override_count(), 1);
+
+        assert_eq!(transformer.transform("arkon"), "archon");
+        assert_eq!(transformer.stats().user_override_matches, 1);
+        assert_eq!(transformer.stats().fuzzy_matches, 0);
+    }
+
+    #[test]
+    fn test_confirm_rejection_blocks_pair() {
+        let config = TransformConfig {
+            learn_on_correction: false,
+            ..TransformConfig::default()
+        };
+        let mut transformer = TransformV3::new(config).unwrap();
+        transformer.learn_correction("arkon".to_string(), "archon".to_string());
+        assert_eq!(transformer.transform("arkon"), "archon");
+
+        assert!(transformer.confirm("arkon", false));
+        assert_eq!(transformer.transform("arkon"), "arkon");
+        assert!(!transformer.confirm("arkon", true));
+        assert_eq!(transformer.user_override_count(), 0);
+    }
+
+    #[cfg(not(feature = "temporal-compare"))]
+    #[test]
+    fn test_learned_corrections_use_edit_distance_fallback() {
+        let config = TransformConfig {
+            learn_on_correction: false,
+            ..TransformConfig::default()
+        };
+        let mut transformer = TransformV3::new(config).unwrap();
+        transformer.learn_correction("arkon".to_string(), "archon".to_string());
+
+        assert_eq!(transformer.transform("arkon"), "archon");
+        assert_eq!(transformer.transform("arkan"), "archon");
+        assert_eq!(transformer.stats().fuzzy_matches, 2);
+    }
+
+    #[test]
+    fn test_confidence_floor_passes_garbage_through() {
+        let mut transformer = TransformV3::new(TransformConfig::default()).unwrap();
+        transformer.learn_correction("hello".to_string(), "HELLO".to_string());
+
+        // Without a floor the distant input snaps to the learned pattern
+        assert_eq!(transformer.transform("hxlqo"), "HELLO");
+
+        let config = TransformConfig::default().with_confidence_floor(0.9);
+        let mut transformer = TransformV3::new(config).unwrap();
+        transformer.learn_correction("hello".to_string(), "HELLO".to_string());
+
+        assert_eq!(transformer.transform("hxlqo"), "hxlqo");
+        assert_eq!(transformer.stats().fuzzy_matches, 0);
+
+        // Exact matches clear the floor
+        assert_eq!(transformer.transform("hello"), "HELLO");
+    }
+
     #[test]
     fn test_mode_switching() {
         let config = TransformConfig::default();
@@ -56,6 +56,12 @@ pub enum TransformError {
 
     #[error("Temporal comparison error: {0}")]
     TemporalError(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    #[error("Invalid UTF-8 input: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
 }
 
 pub type Result<T> = std::result::Result<T, TransformError>;
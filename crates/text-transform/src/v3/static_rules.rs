@@ -193,10 +193,7 @@ impl StaticRules {
 
         // Organize by mode
         if let Some(mode) = &rule.mode {
-            self.rules
-                .entry(mode.clone())
-                .or_insert_with(Vec::new)
-                .push(rule);
+            self.rules.entry(mode.clone()).or_default().push(rule);
         } else {
             self.global_rules.push(rule);
         }
@@ -414,7 +411,7 @@ to = "the"
         let rules = StaticRules::with_defaults();
         let secretary_rules = rules.rules_for_mode(TransformMode::Secretary);
 
-        assert!(secretary_rules.len() > 0);
+        assert!(!secretary_rules.is_empty());
         assert!(secretary_rules.iter().any(|r| r.from == "period"));
     }
 }
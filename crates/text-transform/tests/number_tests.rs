@@ -4,7 +4,9 @@
 //! - "number X" → digit conversion
 //! - Contextual triggers: "line X", "version X", "step X", etc.
 
-use midstreamer_text_transform::transform;
+use midstreamer_text_transform::{
+    transform, transform_with_options, DateFormat, TransformOptions, UnitPlacement, UnitTable,
+};
 
 #[test]
 fn test_v2_numbers_pass_through() {
@@ -86,3 +88,107 @@ fn test_numbers_with_explicit_operators() {
         "total = 23"
     );
 }
+
+#[test]
+fn test_ordinals() {
+    // Single-word and compound ordinals after triggers
+    assert_eq!(transform("step twenty first"), "step 21st");
+    assert_eq!(transform("step twentieth"), "step 20th");
+    assert_eq!(transform("number second"), "2nd");
+    assert_eq!(transform("version thirty third"), "version 33rd");
+    assert_eq!(transform("line eleventh"), "line 11th");
+    assert_eq!(transform("line twelfth"), "line 12th");
+
+    // "the" converts a following ordinal
+    assert_eq!(transform("the third"), "the 3rd");
+    assert_eq!(
+        transform("on the twenty second of May"),
+        "on the 22nd of May"
+    );
+    assert_eq!(transform("the ninetieth"), "the 90th");
+
+    // Ordinals elsewhere pass through; "second" as a time unit is untouched
+    assert_eq!(transform("first things first"), "first things first");
+    assert_eq!(transform("wait one second"), "wait one second");
+    assert_eq!(transform("number one second"), "1 second");
+    assert_eq!(transform("the one"), "the one");
+
+    // Words after the ordinal are still transformed
+    assert_eq!(transform("the first comma"), "the 1st,");
+}
+
+#[test]
+fn test_negative_numbers() {
+    assert_eq!(transform("temperature minus forty"), "temperature -40");
+    assert_eq!(transform("number minus forty two"), "-42");
+    assert_eq!(transform("x equals sign number negative five"), "x = -5");
+    assert_eq!(transform("step minus one period"), "step -1.");
+
+    // Outside a numeric context "minus" stays an operator
+    assert_eq!(transform("c minus d"), "c - d");
+    assert_eq!(transform("forty minus two"), "forty - two");
+}
+
+#[test]
+fn test_units_and_currencies() {
+    let options = TransformOptions::new().with_units(UnitTable::new());
+    let units = |text: &str| transform_with_options(text, &options);
+
+    assert_eq!(units("five dollars"), "$5");
+    assert_eq!(units("a tip of twenty percent period"), "a tip of 20%.");
+    assert_eq!(units("three point five kilograms"), "3.5 kg");
+    assert_eq!(units("forty two euros total"), "€42 total");
+
+    // Unit words without a number pass through
+    assert_eq!(units("dollars and cents"), "dollars and cents");
+    assert_eq!(units("five apples"), "five apples");
+
+    // Locales can swap symbols
+    let euro = TransformOptions::new().with_units(UnitTable::new().with_unit(
+        "dollars",
+        "€",
+        UnitPlacement::Prefix,
+    ));
+    assert_eq!(transform_with_options("five dollars", &euro), "€5");
+
+    // Off by default
+    assert_eq!(transform("five dollars"), "five dollars");
+}
+
+#[test]
+fn test_spoken_dates() {
+    let options = TransformOptions::new().with_date_format(DateFormat::default());
+    let dates = |text: &str| transform_with_options(text, &options);
+
+    assert_eq!(
+        dates("on march fifteenth twenty twenty four"),
+        "on March 15, 2024"
+    );
+    assert_eq!(dates("due march fifteenth period"), "due March 15.");
+    assert_eq!(
+        dates("since july twenty first nineteen sixty nine"),
+        "since July 21, 1969"
+    );
+
+    // Month words without a valid ordinal day pass through
+    assert_eq!(dates("march forward"), "march forward");
+    assert_eq!(dates("may I ask"), "may I ask");
+    assert_eq!(dates("february thirtieth"), "february thirtieth");
+
+    // A single number word after the day is not a year
+    assert_eq!(dates("march first two"), "March 1 two");
+
+    let iso = TransformOptions::new().with_date_format(DateFormat::Iso);
+    assert_eq!(
+        transform_with_options("march fifteenth twenty twenty four", &iso),
+        "2024-03-15"
+    );
+    let day_first = TransformOptions::new().with_date_format(DateFormat::DayMonthYear);
+    assert_eq!(
+        transform_with_options("march fifteenth twenty twenty four", &day_first),
+        "15 March 2024"
+    );
+
+    // Off by default
+    assert_eq!(transform("march fifteenth"), "march fifteenth");
+}
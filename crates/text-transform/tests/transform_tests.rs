@@ -100,6 +100,19 @@ fn test_logical_operators() {
     assert_eq!(transform("a and and b"), "a && b");
     assert_eq!(transform("c double pipe d"), "c || d");
     assert_eq!(transform("c or or d"), "c || d");
+    assert_eq!(transform("x logical and y"), "x && y");
+    assert_eq!(transform("x logical or y"), "x || y");
+    assert_eq!(
+        transform("if a logical and b logical or c"),
+        "if a && b || c"
+    );
+
+    // Single "and"/"or" stay words
+    assert_eq!(
+        transform("salt and pepper or sugar"),
+        "salt and pepper or sugar"
+    );
+    assert_eq!(transform("logical reasoning"), "logical reasoning");
 }
 
 #[test]
@@ -8,7 +8,11 @@
 
 use nalgebra::{DMatrix, DVector};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 
 /// Types of attractors that can be detected
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -90,12 +94,41 @@ impl Trajectory {
     }
 }
 
+/// Hit/miss counts of the `AttractorAnalyzer` result cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+}
+
+impl AnalysisCacheStats {
+    /// Fraction of lookups served from the cache (0.0 if there were none)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Analysis results keyed by a hash of the time series contents
+struct AnalysisCache {
+    entries: Mutex<HashMap<u64, AttractorInfo>>,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
 /// Attractor analyzer using dynamical systems theory
 pub struct AttractorAnalyzer {
     embedding_dimension: usize,
     time_delay: usize,
     min_trajectory_length: usize,
     lyapunov_iterations: usize,
+    cache: Option<AnalysisCache>,
 }
 
 impl AttractorAnalyzer {
@@ -106,6 +139,45 @@ impl AttractorAnalyzer {
             time_delay,
             min_trajectory_length: 100,
             lyapunov_iterations: 100,
+            cache: None,
+        }
+    }
+
+    /// Builder: Cache up to `capacity` results keyed by time series contents
+    ///
+    /// Analyzing a series identical to a cached one returns the stored
+    /// result without recomputing it. When full, an arbitrary entry is
+    /// evicted. A capacity of 0 disables caching.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = (capacity > 0).then(|| AnalysisCache {
+            entries: Mutex::new(HashMap::with_capacity(capacity)),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        });
+        self
+    }
+
+    /// Cache statistics (all zero when caching is disabled)
+    pub fn cache_stats(&self) -> AnalysisCacheStats {
+        match &self.cache {
+            Some(cache) => AnalysisCacheStats {
+                hits: cache.hits.load(Ordering::Relaxed),
+                misses: cache.misses.load(Ordering::Relaxed),
+                size: cache.entries.lock().map_or(0, |entries| entries.len()),
+            },
+            None => AnalysisCacheStats::default(),
+        }
+    }
+
+    /// Drop all cached results and reset the statistics
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            if let Ok(mut entries) = cache.entries.lock() {
+                entries.clear();
+            }
+            cache.hits.store(0, Ordering::Relaxed);
+            cache.misses.store(0, Ordering::Relaxed);
         }
     }
 
@@ -119,6 +191,36 @@ impl AttractorAnalyzer {
             ));
         }
 
+        let Some(cache) = &self.cache else {
+            return Ok(self.analyze_uncached(data));
+        };
+
+        let key = series_hash(data);
+        if let Some(info) = cache
+            .entries
+            .lock()
+            .ok()
+            .and_then(|entries| entries.get(&key).cloned())
+        {
+            cache.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(info);
+        }
+        cache.misses.fetch_add(1, Ordering::Relaxed);
+
+        let info = self.analyze_uncached(data);
+        if let Ok(mut entries) = cache.entries.lock() {
+            if entries.len() >= cache.capacity {
+                if let Some(evicted) = entries.keys().next().copied() {
+                    entries.remove(&evicted);
+                }
+            }
+            entries.insert(key, info.clone());
+        }
+        Ok(info)
+    }
+
+    /// Run the analysis on a series already known to be long enough
+    fn analyze_uncached(&self, data: &[f64]) -> AttractorInfo {
         // Reconstruct phase space
         let trajectory =
             Trajectory::from_timeseries(data, self.embedding_dimension, self.time_delay);
@@ -135,13 +237,13 @@ impl AttractorAnalyzer {
         // Calculate stability
         let stability = self.calculate_stability(&trajectory);
 
-        Ok(AttractorInfo {
+        AttractorInfo {
             attractor_type,
             lyapunov_exponent: lyapunov,
             correlation_dimension: corr_dim,
             is_chaotic: lyapunov > 0.0,
             stability_index: stability,
-        })
+        }
     }
 
     /// Calculate largest Lyapunov exponent (indicator of chaos)
@@ -297,6 +399,16 @@ impl AttractorAnalyzer {
     }
 }
 
+/// Hash of a time series by the exact bits of its values
+fn series_hash(data: &[f64]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.len().hash(&mut hasher);
+    for value in data {
+        value.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
 impl Default for AttractorAnalyzer {
     fn default() -> Self {
         Self::new(3, 1)
@@ -322,6 +434,20 @@ impl BehaviorAttractorAnalyzer {
         }
     }
 
+    /// Builder: Cache up to `capacity` analysis results, so repeated
+    /// stability and chaos checks of unchanged histories are not recomputed
+    ///
+    /// See `AttractorAnalyzer::with_cache`.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.analyzer = self.analyzer.with_cache(capacity);
+        self
+    }
+
+    /// Statistics of the analysis cache (all zero when caching is disabled)
+    pub fn cache_stats(&self) -> AnalysisCacheStats {
+        self.analyzer.cache_stats()
+    }
+
     /// Update with new observation
     pub fn observe(&mut self, reward: f64, confidence: f64) {
         self.reward_history.push_back(reward);
@@ -451,6 +577,34 @@ mod tests {
         println!("Attractor type: {:?}", info.attractor_type);
     }
 
+    #[test]
+    fn test_analysis_cache() {
+        let analyzer = AttractorAnalyzer::new(2, 1).with_cache(8);
+        let data: Vec<f64> = (0..100).map(|i| (i as f64 * 0.1).sin()).collect();
+
+        let first = analyzer.analyze(&data).unwrap();
+        let second = analyzer.analyze(&data).unwrap();
+        assert_eq!(first.attractor_type, second.attractor_type);
+        assert_eq!(first.lyapunov_exponent, second.lyapunov_exponent);
+
+        let stats = analyzer.cache_stats();
+        assert_eq!((stats.hits, stats.misses, stats.size), (1, 1, 1));
+
+        // A different series of the same length is a miss
+        let shifted: Vec<f64> = data.iter().map(|x| x + 1.0).collect();
+        analyzer.analyze(&shifted).unwrap();
+        assert_eq!(analyzer.cache_stats().misses, 2);
+        assert_eq!(analyzer.cache_stats().hit_rate(), 1.0 / 3.0);
+
+        analyzer.clear_cache();
+        assert_eq!(analyzer.cache_stats(), AnalysisCacheStats::default());
+
+        // Caching is opt-in
+        let uncached = AttractorAnalyzer::new(2, 1);
+        uncached.analyze(&data).unwrap();
+        assert_eq!(uncached.cache_stats(), AnalysisCacheStats::default());
+    }
+
     #[test]
     fn test_behavior_analyzer() {
         let mut analyzer = BehaviorAttractorAnalyzer::new(2, 100);
@@ -469,6 +623,19 @@ mod tests {
         assert!(summary.is_stable || summary.history_length > 100);
     }
 
+    #[test]
+    fn test_behavior_analyzer_cache() {
+        let mut analyzer = BehaviorAttractorAnalyzer::new(2, 100).with_cache(4);
+        for i in 0..150 {
+            analyzer.observe((i as f64 * 0.1).sin(), 0.5 + i as f64 / 300.0);
+        }
+
+        // The reward history is analyzed again by `is_stable` and `is_chaotic`
+        analyzer.get_behavior_summary();
+        let stats = analyzer.cache_stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+    }
+
     #[test]
     fn test_prediction() {
         let analyzer = AttractorAnalyzer::new(2, 1);
@@ -45,8 +45,8 @@ pub mod types;
 
 pub use agent::{Action, AgenticLoop, LearningSignal, Observation, Plan};
 pub use attractor::{
-    AttractorAnalyzer, AttractorInfo, AttractorType, BehaviorAttractorAnalyzer, BehaviorSummary,
-    PhasePoint, Trajectory,
+    AnalysisCacheStats, AttractorAnalyzer, AttractorInfo, AttractorType, BehaviorAttractorAnalyzer,
+    BehaviorSummary, PhasePoint, Trajectory,
 };
 pub use knowledge::{Entity, KnowledgeGraph, Relation, TheoremStore};
 pub use learning::{AdaptationStrategy, OnlineModel, StreamLearner};
@@ -7,7 +7,9 @@
 //! - Differentiable temporal logic
 
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::time::Duration;
 
 /// Temporal logic operators
@@ -150,6 +152,21 @@ impl TemporalTrace {
     pub fn get_state(&self, index: usize) -> Option<&TemporalState> {
         self.states.get(index)
     }
+
+    /// Hash of every state's timestamp, confidence and propositions
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.states.len().hash(&mut hasher);
+        for state in &self.states {
+            state.timestamp.hash(&mut hasher);
+            state.confidence.to_bits().hash(&mut hasher);
+
+            let mut propositions: Vec<(&String, &bool)> = state.propositions.iter().collect();
+            propositions.sort_unstable();
+            propositions.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
 }
 
 impl Default for TemporalTrace {
@@ -209,6 +226,10 @@ pub struct TemporalNeuralSolver {
     neural_weights: HashMap<String, f64>,
     /// Verification cache
     cache: HashMap<String, VerificationResult>,
+    /// Number of `verify` calls answered from the cache
+    cache_hits: u64,
+    /// Number of `verify` calls that had to evaluate the formula
+    cache_misses: u64,
 }
 
 impl TemporalNeuralSolver {
@@ -217,6 +238,8 @@ impl TemporalNeuralSolver {
         Self {
             neural_weights: HashMap::new(),
             cache: HashMap::new(),
+            cache_hits: 0,
+            cache_misses: 0,
         }
     }
 
@@ -227,10 +250,12 @@ impl TemporalNeuralSolver {
         trace: &TemporalTrace,
     ) -> VerificationResult {
         // Check cache
-        let cache_key = format!("{:?}", formula);
+        let cache_key = Self::cache_key(formula, trace);
         if let Some(cached) = self.cache.get(&cache_key) {
+            self.cache_hits += 1;
             return cached.clone();
         }
+        self.cache_misses += 1;
 
         // Verify formula
         let result = self.verify_at_position(formula, trace, 0);
@@ -241,6 +266,33 @@ impl TemporalNeuralSolver {
         result
     }
 
+    /// Pre-verify a set of formulas against a trace and cache the results
+    ///
+    /// Later `verify` calls with the same formula and trace are cache hits,
+    /// so verification cost can be paid before a latency-sensitive phase.
+    pub fn warm_cache(&mut self, formulas: &[TemporalFormula], trace: &TemporalTrace) {
+        for formula in formulas {
+            self.verify(formula, trace);
+        }
+    }
+
+    /// Verification cache statistics as `(hits, misses)`
+    pub fn cache_stats(&self) -> (u64, u64) {
+        (self.cache_hits, self.cache_misses)
+    }
+
+    /// Clear cached results and reset the hit/miss counters
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+        self.cache_hits = 0;
+        self.cache_misses = 0;
+    }
+
+    /// Cache key covering both the formula and the trace contents
+    fn cache_key(formula: &TemporalFormula, trace: &TemporalTrace) -> String {
+        format!("{:?}@{:016x}", formula, trace.fingerprint())
+    }
+
     /// Verify formula at a specific position in the trace
     fn verify_at_position(
         &self,
@@ -688,6 +740,60 @@ mod tests {
         println!("Complex formula result: {:?}", result);
     }
 
+    #[test]
+    fn test_warm_cache() {
+        let mut solver = TemporalNeuralSolver::new();
+        let mut trace = TemporalTrace::new();
+
+        for i in 0..5 {
+            let mut state = TemporalState::new(Duration::from_secs(i));
+            state.set("ready".to_string(), i >= 2);
+            trace.add_state(state);
+        }
+
+        let formulas = vec![
+            TemporalFormula::eventually(TemporalFormula::atom("ready")),
+            TemporalFormula::globally(TemporalFormula::atom("ready")),
+        ];
+        solver.warm_cache(&formulas, &trace);
+        assert_eq!(solver.cache_stats().0, 0);
+
+        assert!(solver.verify(&formulas[0], &trace).holds);
+        assert!(!solver.verify(&formulas[1], &trace).holds);
+        assert_eq!(solver.cache_stats().0, 2);
+
+        // A different trace must not reuse results cached for the first one
+        let mut other = TemporalTrace::new();
+        let mut state = TemporalState::new(Duration::from_secs(0));
+        state.set("ready".to_string(), true);
+        other.add_state(state);
+
+        assert!(solver.verify(&formulas[1], &other).holds);
+        assert_eq!(solver.cache_stats().0, 2);
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        let mut solver = TemporalNeuralSolver::new();
+        let mut trace = TemporalTrace::new();
+
+        let mut state = TemporalState::new(Duration::from_secs(0));
+        state.set("safe".to_string(), true);
+        trace.add_state(state);
+
+        let formula = TemporalFormula::atom("safe");
+        solver.verify(&formula, &trace);
+        assert_eq!(solver.cache_stats(), (0, 1));
+
+        solver.verify(&formula, &trace);
+        assert_eq!(solver.cache_stats(), (1, 1));
+
+        solver.clear_cache();
+        assert_eq!(solver.cache_stats(), (0, 0));
+        solver.verify(&formula, &trace);
+        assert_eq!(solver.cache_stats(), (0, 1));
+    }
+
     #[test]
     fn test_learning() {
         let mut solver = TemporalNeuralSolver::new();